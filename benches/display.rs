@@ -0,0 +1,24 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rubiks_cube_solver::{set_color_enabled, RubiksCube};
+
+/// Prints a 50x50x50 net, where formatting is dominated by the 15,000 stickers. The supercube
+/// marks all but a thin ring of each face's stickers as centers, so it times the arrows too.
+fn display_big_cube(c: &mut Criterion) {
+    let mut group = c.benchmark_group("display/50x50x50");
+    for (kind, rc) in [
+        ("plain", RubiksCube::new(50)),
+        ("supercube", RubiksCube::new_supercube(50)),
+    ] {
+        for colors in [true, false] {
+            let label = if colors { "colors" } else { "letters" };
+            group.bench_function(BenchmarkId::new(kind, label), |b| {
+                set_color_enabled(colors);
+                b.iter(|| rc.to_string())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, display_big_cube);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::SeedableRng;
+use rubiks_cube_solver::{scramble, Face, RubiksCube};
+
+/// The straightforward check: every sticker against its face's center, without stopping early.
+fn is_solved_naive(rc: &RubiksCube) -> bool {
+    let size = rc.size();
+    let mut solved = true;
+    for face in Face::ALL {
+        let center = rc.get(face, size / 2, size / 2);
+        for row in 0..size {
+            for col in 0..size {
+                solved &= rc.get(face, row, col) == center;
+            }
+        }
+    }
+    solved
+}
+
+/// A solved cube is the slowest case for both, as every sticker has to be looked at; a
+/// scrambled one shows how soon the early exit pays off.
+fn is_solved_by_size(c: &mut Criterion) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(574);
+    for size in [3, 20] {
+        let mut group = c.benchmark_group(format!("is_solved/{size}x{size}x{size}"));
+        let mut scrambled = RubiksCube::new(size);
+        scrambled.apply_moves(&scramble(size, 60, &mut rng));
+
+        for (state, rc) in [("solved", RubiksCube::new(size)), ("scrambled", scrambled)] {
+            group.bench_function(BenchmarkId::new("early_exit", state), |b| {
+                b.iter(|| rc.is_solved())
+            });
+            group.bench_function(BenchmarkId::new("naive", state), |b| {
+                b.iter(|| is_solved_naive(&rc))
+            });
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, is_solved_by_size);
+criterion_main!(benches);
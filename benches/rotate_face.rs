@@ -0,0 +1,32 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use rubiks_cube_solver::{rotate_face, Face, Movement, RubiksCube};
+
+const SIZES: [usize; 5] = [3, 5, 10, 20, 50];
+
+/// Each benchmark times a single turn, so Criterion's throughput figure is moves per second.
+fn rotate_face_by_size(c: &mut Criterion) {
+    for size in SIZES {
+        let mut group = c.benchmark_group(format!("rotate_face/{size}x{size}x{size}"));
+        group.throughput(Throughput::Elements(1));
+
+        for movement in [
+            Movement::Clockwise,
+            Movement::CounterClockwise,
+            Movement::Half,
+        ] {
+            // The outer layer also turns the face's own grid; the middle layer only cycles
+            // the rows and columns of the four sides around it.
+            for (layer, depth) in [("face", 0), ("deep", size / 2)] {
+                let mut rc = RubiksCube::new(size);
+                group.bench_function(BenchmarkId::new(format!("{movement:?}"), layer), |b| {
+                    b.iter(|| rotate_face(&mut rc, Face::Right, movement, depth))
+                });
+            }
+        }
+
+        group.finish();
+    }
+}
+
+criterion_group!(benches, rotate_face_by_size);
+criterion_main!(benches);
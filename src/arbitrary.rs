@@ -0,0 +1,112 @@
+//! [`proptest`] strategies for the crate's types, so property tests can take random faces,
+//! movements and cubes as arguments.
+
+use proptest::prelude::*;
+
+use crate::{rotate_face, Face, Movement, RubiksCube};
+
+/// The largest cube [`RubiksCube`]'s [`Arbitrary`] impl makes.
+pub const MAX_ARBITRARY_SIZE: usize = 5;
+
+impl Arbitrary for Face {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Face>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (0..6usize)
+            .prop_map(|i| num_traits::FromPrimitive::from_usize(i).unwrap())
+            .boxed()
+    }
+}
+
+impl Arbitrary for Movement {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Movement>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        prop_oneof![
+            Just(Movement::Clockwise),
+            Just(Movement::CounterClockwise),
+            Just(Movement::Half),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for RubiksCube {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<RubiksCube>;
+
+    /// A cube of size 1 to [`MAX_ARBITRARY_SIZE`], scrambled by up to 30 random turns of any
+    /// layer, so it's always a state a real cube can be in.
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1..=MAX_ARBITRARY_SIZE)
+            .prop_flat_map(|size| {
+                let turn = (any::<Face>(), any::<Movement>(), 0..size);
+                proptest::collection::vec(turn, 0..30).prop_map(move |turns| {
+                    let mut rc = RubiksCube::new(size);
+                    for (face, movement, depth) in turns {
+                        rotate_face(&mut rc, face, movement, depth);
+                    }
+                    rc
+                })
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn moved_then_inverse_restores(
+            rc: crate::RubiksCube,
+            face: crate::Face,
+            movement: crate::Movement,
+            depth: prop::sample::Index,
+        ) {
+            let depth = depth.index(rc.size());
+            let turned = crate::moved(&rc, face, movement, depth);
+            let [(face, movement, depth)] = crate::invert(&[(face, movement, depth)])[..] else {
+                unreachable!()
+            };
+            prop_assert_eq!(crate::moved(&turned, face, movement, depth), rc);
+        }
+
+        #[test]
+        fn turns_keep_color_counts(
+            rc: crate::RubiksCube,
+            face: crate::Face,
+            movement: crate::Movement,
+            depth: prop::sample::Index,
+        ) {
+            let color_counts = |rc: &crate::RubiksCube| {
+                let mut counts = [0; 6];
+                for (.., color) in rc.stickers() {
+                    counts[color as usize] += 1;
+                }
+                counts
+            };
+            let depth = depth.index(rc.size());
+            let turned = crate::moved(&rc, face, movement, depth);
+            prop_assert_eq!(color_counts(&turned), color_counts(&rc));
+        }
+
+        #[test]
+        fn is_solved_matches_checking_every_sticker(rc: crate::RubiksCube, scrambled: bool) {
+            let mut rc = rc;
+            if !scrambled {
+                rc = crate::RubiksCube::new(rc.size());
+            }
+            let center = |face| rc.get(face, rc.size() / 2, rc.size() / 2);
+            let every_sticker_matches = rc
+                .stickers()
+                .filter(|&(face, _, _, color)| color != center(face))
+                .count()
+                == 0;
+            prop_assert_eq!(rc.is_solved(), every_sticker_matches);
+        }
+    }
+}
@@ -0,0 +1,321 @@
+use crate::f2l::{
+    in_place, locate, pieces, slot_pieces, triggers, up_turns, F2lSlot, Sticker, LIFTS,
+};
+use crate::solver::search_steps;
+use crate::{rotate_face, Face, Move, MoveToken, Movement, RubiksCube, SolveObserver};
+
+use Face::*;
+use Movement::*;
+
+type Steps = Vec<Vec<(Face, Movement)>>;
+
+/// The side faces in the order the up face turns them past the front.
+const SIDES: [Face; 4] = [Front, Right, Back, Left];
+
+/// An algorithm in standard notation, made only of outer face turns.
+fn alg(notation: &str) -> Vec<(Face, Movement)> {
+    notation
+        .split_whitespace()
+        .map(|m| match m.parse() {
+            Ok(MoveToken::Turn(face, movement, 0)) => (face, movement),
+            _ => panic!("{m:?} isn't an outer face turn"),
+        })
+        .collect()
+}
+
+fn inverse(moves: &[(Face, Movement)]) -> Vec<(Face, Movement)> {
+    let moves: Vec<_> = moves.iter().map(|&(f, m)| (f, m, 0)).collect();
+    crate::invert(&moves)
+        .into_iter()
+        .map(|(f, m, _)| (f, m))
+        .collect()
+}
+
+fn center(rc: &RubiksCube, face: Face) -> crate::Color {
+    rc.get(face, 1, 1)
+}
+
+fn cross_edge(rc: &RubiksCube, pieces: &[Vec<Sticker>], side: Face) -> Vec<Sticker> {
+    locate(rc, pieces, &[center(rc, Down), center(rc, side)])
+}
+
+fn slot_corner(rc: &RubiksCube, pieces: &[Vec<Sticker>], slot: F2lSlot) -> Vec<Sticker> {
+    let [a, b] = slot.sides().map(|face| center(rc, face));
+    locate(rc, pieces, &[center(rc, Down), a, b])
+}
+
+fn slot_edge(rc: &RubiksCube, pieces: &[Vec<Sticker>], slot: F2lSlot) -> Vec<Sticker> {
+    let [a, b] = slot.sides().map(|face| center(rc, face));
+    locate(rc, pieces, &[a, b])
+}
+
+/// Puts the down edge of each side in place in turn. Sides whose edge is already in place are
+/// only turned away and straight back around an up turn, so their edge never leaves the down
+/// layer for good.
+fn solve_cross(rc: &mut RubiksCube, pieces: &[Vec<Sticker>]) -> Option<Vec<(Face, Movement)>> {
+    let mut moves = Vec::new();
+    for target in SIDES {
+        let mut steps = up_turns();
+        for side in SIDES {
+            if side != target && in_place(rc, &cross_edge(rc, pieces, side)) {
+                steps.extend(triggers(side, Clockwise));
+                steps.extend(triggers(side, CounterClockwise));
+            } else {
+                steps.extend([Clockwise, CounterClockwise, Half].map(|m| vec![(side, m)]));
+            }
+        }
+        let found = search_steps(
+            rc,
+            &steps,
+            |cube| cross_edge(cube, pieces, target),
+            |cube, stickers| in_place(cube, stickers),
+        )?;
+        apply(rc, &found, &mut moves);
+    }
+    Some(moves)
+}
+
+/// Puts the down corner of each slot in place with triggers that only open unsolved slots.
+fn solve_first_layer(
+    rc: &mut RubiksCube,
+    pieces: &[Vec<Sticker>],
+) -> Option<Vec<(Face, Movement)>> {
+    let mut moves = Vec::new();
+    for target in F2lSlot::ALL {
+        let mut steps = up_turns();
+        for (face, lift, lifted) in LIFTS {
+            if lifted == target || !in_place(rc, &slot_corner(rc, pieces, lifted)) {
+                steps.extend(triggers(face, lift));
+            }
+        }
+        let found = search_steps(
+            rc,
+            &steps,
+            |cube| slot_corner(cube, pieces, target),
+            |cube, stickers| in_place(cube, stickers),
+        )?;
+        apply(rc, &found, &mut moves);
+    }
+    Some(moves)
+}
+
+/// Inserts the edge of each slot from the up layer with `U R U' R' U' F' U F` and its mirror,
+/// which leave the first layer and the other slots alone.
+fn solve_second_layer(
+    rc: &mut RubiksCube,
+    pieces: &[Vec<Sticker>],
+) -> Option<Vec<(Face, Movement)>> {
+    let mut inserts = Vec::new();
+    for (i, &front) in SIDES.iter().enumerate() {
+        let right = SIDES[(i + 1) % 4];
+        let slot = F2lSlot::ALL
+            .into_iter()
+            .find(|slot| slot.sides().contains(&front) && slot.sides().contains(&right))
+            .unwrap();
+        let into_right = [
+            (Up, Clockwise),
+            (right, Clockwise),
+            (Up, CounterClockwise),
+            (right, CounterClockwise),
+            (Up, CounterClockwise),
+            (front, CounterClockwise),
+            (Up, Clockwise),
+            (front, Clockwise),
+        ];
+        let into_left = [
+            (Up, CounterClockwise),
+            (front, CounterClockwise),
+            (Up, Clockwise),
+            (front, Clockwise),
+            (Up, Clockwise),
+            (right, Clockwise),
+            (Up, CounterClockwise),
+            (right, CounterClockwise),
+        ];
+        inserts.push((slot, into_right.to_vec()));
+        inserts.push((slot, into_left.to_vec()));
+    }
+
+    let mut moves = Vec::new();
+    for target in F2lSlot::ALL {
+        let mut steps = up_turns();
+        for (slot, insert) in &inserts {
+            if *slot == target || !in_place(rc, &slot_pieces(rc, pieces, *slot)) {
+                steps.push(insert.clone());
+            }
+        }
+        let found = search_steps(
+            rc,
+            &steps,
+            |cube| slot_edge(cube, pieces, target),
+            |cube, stickers| in_place(cube, stickers),
+        )?;
+        apply(rc, &found, &mut moves);
+    }
+    Some(moves)
+}
+
+/// Runs a last layer step: `algs` (each also tried backwards) and up turns until `done`.
+fn solve_last_layer_step(
+    rc: &mut RubiksCube,
+    algs: &[&str],
+    done: impl Fn(&RubiksCube) -> bool,
+) -> Option<Vec<(Face, Movement)>> {
+    let mut steps: Steps = up_turns();
+    for notation in algs {
+        steps.push(alg(notation));
+        steps.push(inverse(&alg(notation)));
+    }
+    let found = search_steps(rc, &steps, |cube| cube.faces.clone(), |cube, _| done(cube))?;
+    let mut moves = Vec::new();
+    apply(rc, &found, &mut moves);
+    Some(moves)
+}
+
+fn up_face_matches(rc: &RubiksCube, stickers: &[(usize, usize)]) -> bool {
+    stickers
+        .iter()
+        .all(|&(row, col)| rc.get(Up, row, col) == center(rc, Up))
+}
+
+/// Whether the up layer's corners are in place relative to each other, so that only up turns
+/// are needed to finish them.
+fn up_corners_placed(rc: &RubiksCube) -> bool {
+    let corners = [(Front, 0, 0), (Front, 0, 2), (Back, 0, 0), (Back, 0, 2)];
+    let mut turned = rc.clone();
+    (0..4).any(|_| {
+        rotate_face(&mut turned, Up, Clockwise, 0);
+        corners
+            .iter()
+            .all(|&(face, row, col)| turned.get(face, row, col) == center(&turned, face))
+            && [Left, Right].iter().all(|&face| {
+                [0, 2]
+                    .iter()
+                    .all(|&col| turned.get(face, 0, col) == center(&turned, face))
+            })
+    })
+}
+
+fn apply(rc: &mut RubiksCube, found: &[(Face, Movement)], moves: &mut Vec<(Face, Movement)>) {
+    for &(face, movement) in found {
+        rotate_face(rc, face, movement, 0);
+    }
+    moves.extend_from_slice(found);
+}
+
+impl RubiksCube {
+    /// Solves a 3x3x3 the way the beginner's method does, layer by layer: the down cross, the
+    /// down corners, the middle edges, then the up layer with a few well-known algorithms. The
+    /// solutions are long but every stage is easy to follow. Returns `None` for other sizes and
+    /// for cubes that fail [`RubiksCube::is_valid`].
+    pub fn solve_beginner(&self) -> Option<Vec<Move>> {
+        self.solve_3x3_explained(&mut ())
+    }
+
+    /// Like [`RubiksCube::solve_beginner`], telling `observer` about each stage as it's done.
+    pub fn solve_3x3_explained(&self, observer: &mut impl SolveObserver) -> Option<Vec<Move>> {
+        if self.size != 3 || self.is_valid().is_err() {
+            return None;
+        }
+
+        let mut rc = self.clone();
+        let pieces = pieces();
+        let mut solution = Vec::new();
+        let mut stage = |name: &str, moves: Vec<(Face, Movement)>| {
+            observer.on_stage(name, &moves);
+            solution.extend(moves.into_iter().map(|(face, movement)| Move {
+                face,
+                movement,
+                depth: 0,
+            }));
+        };
+
+        stage("cross", solve_cross(&mut rc, &pieces)?);
+        stage("first layer", solve_first_layer(&mut rc, &pieces)?);
+        stage("second layer", solve_second_layer(&mut rc, &pieces)?);
+        stage(
+            "last layer cross",
+            solve_last_layer_step(&mut rc, &["F R U R' U' F'"], |cube| {
+                up_face_matches(cube, &[(0, 1), (1, 0), (1, 2), (2, 1)])
+            })?,
+        );
+        stage(
+            "last layer face",
+            solve_last_layer_step(&mut rc, &["R U R' U R U2 R'"], |cube| {
+                up_face_matches(cube, &[(0, 0), (0, 2), (2, 0), (2, 2)])
+                    && up_face_matches(cube, &[(0, 1), (1, 0), (1, 2), (2, 1)])
+            })?,
+        );
+        stage(
+            "last layer corners",
+            solve_last_layer_step(&mut rc, &["R' F R' B2 R F' R' B2 R2"], up_corners_placed)?,
+        );
+        stage(
+            "last layer edges",
+            solve_last_layer_step(&mut rc, &["R U' R U R U R U' R' U' R2"], |cube| {
+                cube.is_solved()
+            })?,
+        );
+
+        Some(solution)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn solve_beginner_scrambles() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(17);
+        for _ in 0..10 {
+            let mut rc = crate::RubiksCube::new(3);
+            for _ in 0..25 {
+                let face = num_traits::FromPrimitive::from_usize(rng.gen_range(0..6)).unwrap();
+                let movement = [
+                    crate::Movement::Clockwise,
+                    crate::Movement::CounterClockwise,
+                    crate::Movement::Half,
+                ][rng.gen_range(0..3)];
+                crate::rotate_face(&mut rc, face, movement, 0);
+            }
+
+            let solution = rc.solve_beginner().unwrap();
+            for m in solution {
+                crate::rotate_face(&mut rc, m.face, m.movement, m.depth);
+            }
+            assert!(rc.is_solved());
+        }
+
+        assert_eq!(crate::RubiksCube::new(3).solve_beginner(), Some(vec![]));
+        assert_eq!(crate::RubiksCube::new(2).solve_beginner(), None);
+    }
+
+    #[test]
+    fn observer_sees_each_stage() {
+        struct Recorder(Vec<String>);
+
+        impl crate::SolveObserver for Recorder {
+            fn on_stage(&mut self, name: &str, _: &[(crate::Face, crate::Movement)]) {
+                self.0.push(name.to_string());
+            }
+        }
+
+        let mut rc = crate::RubiksCube::new(3);
+        rc.apply_wca("R U F' L2 D B' R2").unwrap();
+        let mut recorder = Recorder(Vec::new());
+        rc.solve_3x3_explained(&mut recorder).unwrap();
+        assert_eq!(
+            recorder.0,
+            [
+                "cross",
+                "first layer",
+                "second layer",
+                "last layer cross",
+                "last layer face",
+                "last layer corners",
+                "last layer edges",
+            ]
+        );
+    }
+}
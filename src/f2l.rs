@@ -0,0 +1,198 @@
+use std::collections::HashMap;
+
+use crate::solver::search_steps;
+use crate::{sticker_position, Color, Face, Movement, RubiksCube};
+
+use Face::*;
+use Movement::*;
+
+/// One of the four first-two-layers slots, named by the side faces it sits between.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum F2lSlot {
+    FrontRight,
+    FrontLeft,
+    BackRight,
+    BackLeft,
+}
+
+impl F2lSlot {
+    pub(crate) const ALL: [F2lSlot; 4] = [
+        F2lSlot::FrontRight,
+        F2lSlot::FrontLeft,
+        F2lSlot::BackRight,
+        F2lSlot::BackLeft,
+    ];
+
+    pub(crate) fn sides(self) -> [Face; 2] {
+        match self {
+            F2lSlot::FrontRight => [Front, Right],
+            F2lSlot::FrontLeft => [Front, Left],
+            F2lSlot::BackRight => [Back, Right],
+            F2lSlot::BackLeft => [Back, Left],
+        }
+    }
+}
+
+/// Side turns that lift a slot into the up layer, with the slot they lift. Following one with
+/// an up turn and undoing it only moves pieces of that slot and the up layer.
+pub(crate) const LIFTS: [(Face, Movement, F2lSlot); 8] = [
+    (Right, Clockwise, F2lSlot::FrontRight),
+    (Right, CounterClockwise, F2lSlot::BackRight),
+    (Front, Clockwise, F2lSlot::FrontLeft),
+    (Front, CounterClockwise, F2lSlot::FrontRight),
+    (Left, Clockwise, F2lSlot::BackLeft),
+    (Left, CounterClockwise, F2lSlot::FrontLeft),
+    (Back, Clockwise, F2lSlot::BackRight),
+    (Back, CounterClockwise, F2lSlot::BackLeft),
+];
+
+pub(crate) type Sticker = (Face, usize, usize);
+
+/// The up turns, each as a one-move step for [`search_steps`].
+pub(crate) fn up_turns() -> Vec<Vec<(Face, Movement)>> {
+    [Clockwise, CounterClockwise, Half]
+        .map(|turn| vec![(Up, turn)])
+        .to_vec()
+}
+
+/// `face` turned by `lift`, then each up turn, then `face` turned back.
+pub(crate) fn triggers(face: Face, lift: Movement) -> [Vec<(Face, Movement)>; 3] {
+    [Clockwise, CounterClockwise, Half]
+        .map(|turn| vec![(face, lift), (Up, turn), (face, lift.inverse())])
+}
+
+/// The stickers of each piece of a 3x3x3, grouped by where they sit.
+pub(crate) fn pieces() -> Vec<Vec<Sticker>> {
+    let mut pieces: HashMap<_, Vec<Sticker>> = HashMap::new();
+    for (face, row, col, _) in RubiksCube::new(3).stickers() {
+        pieces
+            .entry(sticker_position(3, face, row, col))
+            .or_default()
+            .push((face, row, col));
+    }
+    pieces.into_values().collect()
+}
+
+/// Where the stickers of the piece with exactly `colors` are, in the same order as `colors`.
+/// Only called on cubes that pass [`RubiksCube::is_valid`], which have every piece.
+pub(crate) fn locate(rc: &RubiksCube, pieces: &[Vec<Sticker>], colors: &[Color]) -> Vec<Sticker> {
+    let color = |&(face, row, col): &Sticker| rc.get(face, row, col);
+    let piece = pieces
+        .iter()
+        .find(|piece| {
+            piece.len() == colors.len() && piece.iter().all(|s| colors.contains(&color(s)))
+        })
+        .expect("a valid cube has every piece");
+    colors
+        .iter()
+        .map(|&c| *piece.iter().find(|s| color(s) == c).unwrap())
+        .collect()
+}
+
+/// The stickers of a slot's corner and edge, corner first, each in down, first side, second
+/// side order.
+pub(crate) fn slot_pieces(rc: &RubiksCube, pieces: &[Vec<Sticker>], slot: F2lSlot) -> Vec<Sticker> {
+    let center = |face: Face| rc.get(face, 1, 1);
+    let [a, b] = slot.sides().map(center);
+    let mut stickers = locate(rc, pieces, &[center(Down), a, b]);
+    stickers.extend(locate(rc, pieces, &[a, b]));
+    stickers
+}
+
+/// Whether every one of `stickers` matches the center of its face.
+pub(crate) fn in_place(rc: &RubiksCube, stickers: &[Sticker]) -> bool {
+    stickers
+        .iter()
+        .all(|&(face, row, col)| rc.get(face, row, col) == rc.get(face, 1, 1))
+}
+
+/// Finds the moves that put the corner and edge of `slot` in place on a 3x3x3, leaving the
+/// down layer edges and every other solved slot where they are. Slots that aren't solved yet
+/// may be moved around along the way.
+///
+/// The search only uses up turns and short triggers such as `R U R'` that each open one
+/// unsolved slot, so nothing outside the up layer and the unsolved slots is ever touched.
+/// Returns `None` for other sizes and for cubes that fail [`RubiksCube::is_valid`].
+pub fn solve_f2l_pair(rc: &RubiksCube, slot: F2lSlot) -> Option<Vec<(Face, Movement)>> {
+    if rc.size != 3 || rc.is_valid().is_err() {
+        return None;
+    }
+
+    let pieces = pieces();
+    let solved_slots: Vec<_> = F2lSlot::ALL
+        .into_iter()
+        .filter(|&s| s != slot && in_place(rc, &slot_pieces(rc, &pieces, s)))
+        .collect();
+    let mut steps = up_turns();
+    for (face, lift, lifted) in LIFTS {
+        if !solved_slots.contains(&lifted) {
+            steps.extend(triggers(face, lift));
+        }
+    }
+
+    search_steps(
+        rc,
+        &steps,
+        |cube| slot_pieces(cube, &pieces, slot),
+        |cube, stickers| in_place(cube, stickers),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn solve_every_slot_in_turn() {
+        use crate::f2l::{in_place, pieces, slot_pieces, F2lSlot, LIFTS};
+        use crate::Face::*;
+        use crate::Movement::*;
+        use crate::{rotate_face, solve_f2l_pair, RubiksCube};
+        use rand::{Rng, SeedableRng};
+
+        let first_two_layers_solved = |rc: &RubiksCube| {
+            [Left, Front, Right, Back, Down].into_iter().all(|face| {
+                let rows = if face == Down { 0..3 } else { 1..3 };
+                rows.flat_map(|row| (0..3).map(move |col| (row, col)))
+                    .all(|(row, col)| rc.get(face, row, col) == rc.get(face, 1, 1))
+            })
+        };
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(5);
+        for _ in 0..10 {
+            // Scramble with triggers only, so the cross stays solved.
+            let mut rc = RubiksCube::new(3);
+            for _ in 0..20 {
+                let (face, lift, _) = LIFTS[rng.gen_range(0..LIFTS.len())];
+                let turn = [Clockwise, CounterClockwise, Half][rng.gen_range(0..3)];
+                for (face, movement) in [(face, lift), (Up, turn), (face, lift.inverse())] {
+                    rotate_face(&mut rc, face, movement, 0);
+                }
+            }
+            assert!(!first_two_layers_solved(&rc));
+
+            let mut done = Vec::new();
+            for slot in F2lSlot::ALL {
+                for (face, movement) in solve_f2l_pair(&rc, slot).unwrap() {
+                    rotate_face(&mut rc, face, movement, 0);
+                }
+                done.push(slot);
+                assert!(
+                    done.iter()
+                        .all(|&slot| in_place(&rc, &slot_pieces(&rc, &pieces(), slot))),
+                    "{done:?}"
+                );
+            }
+            assert!(first_two_layers_solved(&rc), "{rc}");
+        }
+
+        assert_eq!(
+            solve_f2l_pair(&RubiksCube::new(3), F2lSlot::BackLeft),
+            Some(vec![])
+        );
+        assert_eq!(solve_f2l_pair(&RubiksCube::new(4), F2lSlot::BackLeft), None);
+
+        // With its edge's front sticker recolored, the front left pair can't be found at all.
+        let mut missing = RubiksCube::new(3);
+        missing.set(Front, 1, 0, crate::Color::Yellow);
+        assert_eq!(solve_f2l_pair(&missing, F2lSlot::FrontLeft), None);
+    }
+}
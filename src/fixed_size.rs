@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::{solved_color, turn_layer, Axis, Color, Face, Movement, RubiksCube, Stickers};
+
+/// A cube whose size is known at compile time, so its stickers live in fixed-size arrays
+/// instead of on the heap. It turns the same way as [`RubiksCube`], which is still needed for
+/// sizes only known at run time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Cube<const N: usize> {
+    faces: [[[Color; N]; N]; 6],
+}
+
+impl<const N: usize> Cube<N> {
+    pub fn new() -> Cube<N> {
+        Cube {
+            faces: Face::ALL.map(|face| [[solved_color(face); N]; N]),
+        }
+    }
+
+    /// The color of the sticker at `row`, `col` of `face`, laid out as in
+    /// [`RubiksCube::get`].
+    pub fn get(&self, face: Face, row: usize, col: usize) -> Color {
+        self.faces[face as usize][row][col]
+    }
+
+    pub fn set(&mut self, face: Face, row: usize, col: usize, color: Color) {
+        self.faces[face as usize][row][col] = color;
+    }
+
+    pub fn is_solved(&self) -> bool {
+        self.faces
+            .iter()
+            .all(|face| face.iter().flatten().all(|&color| color == face[0][0]))
+    }
+
+    /// Turns one layer, as [`rotate_face`](crate::rotate_face) does for a [`RubiksCube`].
+    ///
+    /// Panics if `depth` isn't less than `N`.
+    pub fn rotate_face(&mut self, face: Face, movement: Movement, depth: usize) {
+        assert!(
+            depth < N,
+            "depth {depth} is out of range for {face:?} on a {N}x{N}x{N} cube"
+        );
+        turn_layer(self, face, movement, depth);
+    }
+
+    /// Turns the whole cube around `axis`, as [`RubiksCube::rotate_cube`] does.
+    pub fn rotate_cube(&mut self, axis: Axis, movement: Movement) {
+        for depth in 0..N {
+            self.rotate_face(axis.faces().0, movement, depth);
+        }
+    }
+}
+
+impl<const N: usize> Default for Cube<N> {
+    fn default() -> Self {
+        Cube::new()
+    }
+}
+
+impl<const N: usize> Stickers for Cube<N> {
+    type Sticker = Color;
+
+    fn size(&self) -> usize {
+        N
+    }
+
+    fn get(&self, face: Face, row: usize, col: usize) -> Color {
+        Cube::get(self, face, row, col)
+    }
+
+    fn set(&mut self, face: Face, row: usize, col: usize, color: Color) {
+        Cube::set(self, face, row, col, color)
+    }
+}
+
+impl<const N: usize> From<Cube<N>> for RubiksCube {
+    fn from(cube: Cube<N>) -> Self {
+        RubiksCube {
+            size: N,
+            faces: cube.faces.map(|face| face.concat()),
+            history: Some(Vec::new()),
+            orientations: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn turns_match_the_dynamic_cube() {
+        use crate::Movement::*;
+
+        fn check<const N: usize>() {
+            let mut cube = crate::Cube::<N>::new();
+            let mut rc = crate::RubiksCube::new(N);
+            assert_eq!(crate::RubiksCube::from(cube), rc);
+
+            for (i, face) in crate::Face::ALL.into_iter().enumerate() {
+                for (j, movement) in [Clockwise, CounterClockwise, Half].into_iter().enumerate() {
+                    let depth = (i + j) % N;
+                    cube.rotate_face(face, movement, depth);
+                    crate::rotate_face(&mut rc, face, movement, depth);
+                    assert_eq!(
+                        crate::RubiksCube::from(cube),
+                        rc,
+                        "{face:?} {movement:?} {depth}"
+                    );
+                }
+            }
+            assert_eq!(cube.is_solved(), rc.is_solved());
+
+            cube.rotate_cube(crate::Axis::Z, Clockwise);
+            rc.rotate_cube(crate::Axis::Z, Clockwise);
+            assert_eq!(crate::RubiksCube::from(cube), rc);
+        }
+
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<5>();
+    }
+}
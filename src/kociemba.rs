@@ -0,0 +1,786 @@
+//! Kociemba's two-phase algorithm for the 3x3x3.
+//!
+//! Phase 1 turns the cube into the subgroup G1 = <U, D, R2, L2, F2, B2>, where every corner and
+//! edge is oriented and the four middle-layer edges are in the middle layer. Phase 2 solves it
+//! using only those moves. Each phase searches small coordinates of the cube with IDA*, guided
+//! by pruning tables of exact distances in a pair of coordinates.
+
+use std::collections::VecDeque;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+use crate::{rotate_face, simplify, Face, Move, Movement, RubiksCube};
+
+/// The faces in facelet order, which is also the order of the 18 moves: `3 * face + turns - 1`
+/// for 1 to 3 clockwise quarter turns.
+const FACES: [Face; 6] = [
+    Face::Up,
+    Face::Right,
+    Face::Front,
+    Face::Down,
+    Face::Left,
+    Face::Back,
+];
+
+/// The letter naming each face in a facelet string, in the order of [`FACES`].
+const FACE_LETTERS: [char; 6] = ['U', 'R', 'F', 'D', 'L', 'B'];
+
+const MOVES: usize = 18;
+
+/// The moves that stay in G1: U, U2, U', R2, F2, D, D2, D', L2, B2.
+const PHASE_2_MOVES: [usize; 10] = [0, 1, 2, 4, 7, 9, 10, 11, 13, 16];
+
+/// Phase 2 never needs more than 18 moves.
+const MAX_PHASE_2_DEPTH: usize = 18;
+
+/// How many phase 1 depths past the first one that gives a solution are still searched for a
+/// shorter one. Each one costs several times more than the last for about a move less.
+const EXTRA_PHASE_1_DEPTH: usize = 1;
+
+/// The facelets of each corner, clockwise starting from its up or down sticker, as indices
+/// into a facelet string. Corners are URF, UFL, ULB, UBR, DFR, DLF, DBL, DRB.
+const CORNER_FACELETS: [[usize; 3]; 8] = [
+    [8, 9, 20],
+    [6, 18, 38],
+    [0, 36, 47],
+    [2, 45, 11],
+    [29, 26, 15],
+    [27, 44, 24],
+    [33, 53, 42],
+    [35, 17, 51],
+];
+
+/// The facelets of each edge, as indices into a facelet string. Edges are UR, UF, UL, UB, DR,
+/// DF, DL, DB, FR, FL, BL, BR.
+const EDGE_FACELETS: [[usize; 2]; 12] = [
+    [5, 10],
+    [7, 19],
+    [3, 37],
+    [1, 46],
+    [32, 16],
+    [28, 25],
+    [30, 43],
+    [34, 52],
+    [23, 12],
+    [21, 41],
+    [50, 39],
+    [48, 14],
+];
+
+/// The faces each corner's stickers belong to, in the same order as [`CORNER_FACELETS`].
+const CORNER_COLORS: [[usize; 3]; 8] = [
+    [0, 1, 2],
+    [0, 2, 4],
+    [0, 4, 5],
+    [0, 5, 1],
+    [3, 2, 1],
+    [3, 4, 2],
+    [3, 5, 4],
+    [3, 1, 5],
+];
+
+/// The faces each edge's stickers belong to, in the same order as [`EDGE_FACELETS`].
+const EDGE_COLORS: [[usize; 2]; 12] = [
+    [0, 1],
+    [0, 2],
+    [0, 4],
+    [0, 5],
+    [3, 1],
+    [3, 2],
+    [3, 4],
+    [3, 5],
+    [2, 1],
+    [2, 4],
+    [5, 4],
+    [5, 1],
+];
+
+/// The cube as pieces: for each corner and edge position, which piece is there and how it's
+/// twisted or flipped.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) struct CubieCube {
+    pub(crate) cp: [u8; 8],
+    pub(crate) co: [u8; 8],
+    pub(crate) ep: [u8; 12],
+    pub(crate) eo: [u8; 12],
+}
+
+impl CubieCube {
+    pub(crate) const SOLVED: CubieCube = CubieCube {
+        cp: [0, 1, 2, 3, 4, 5, 6, 7],
+        co: [0; 8],
+        ep: [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+        eo: [0; 12],
+    };
+
+    /// Reads the pieces from a facelet string of a valid cube.
+    pub(crate) fn from_facelets(facelets: &str) -> Option<CubieCube> {
+        let faces: Vec<usize> = facelets
+            .chars()
+            .map(|c| "URFDLB".find(c))
+            .collect::<Option<_>>()?;
+        let mut cube = CubieCube::SOLVED;
+        for (i, stickers) in CORNER_FACELETS.iter().enumerate() {
+            let ori = (0..3).find(|&o| matches!(faces[stickers[o]], 0 | 3))?;
+            let next = [1, 2].map(|k| faces[stickers[(ori + k) % 3]]);
+            cube.cp[i] = CORNER_COLORS
+                .iter()
+                .position(|c| c[1..] == next)
+                .map(|j| j as u8)?;
+            cube.co[i] = ori as u8;
+        }
+        for (i, stickers) in EDGE_FACELETS.iter().enumerate() {
+            let colors = stickers.map(|s| faces[s]);
+            let (j, flip) = EDGE_COLORS.iter().enumerate().find_map(|(j, &c)| {
+                if c == colors {
+                    Some((j, 0))
+                } else if c == [colors[1], colors[0]] {
+                    Some((j, 1))
+                } else {
+                    None
+                }
+            })?;
+            cube.ep[i] = j as u8;
+            cube.eo[i] = flip;
+        }
+        Some(cube)
+    }
+
+    /// A cube picked uniformly from every solvable arrangement of the pieces: any order of
+    /// the corners and of the edges as long as the two orders have the same parity, and any
+    /// twists and flips that add up to whole turns.
+    fn random(rng: &mut impl Rng) -> CubieCube {
+        let mut cube = CubieCube::SOLVED;
+        cube.cp.shuffle(rng);
+        cube.ep.shuffle(rng);
+        if is_odd(&cube.cp) != is_odd(&cube.ep) {
+            cube.ep.swap(0, 1);
+        }
+        for i in 0..7 {
+            cube.co[i] = rng.gen_range(0..3);
+        }
+        cube.co[7] = (3 - cube.co[..7].iter().sum::<u8>() % 3) % 3;
+        for i in 0..11 {
+            cube.eo[i] = rng.gen_range(0..2);
+        }
+        cube.eo[11] = cube.eo[..11].iter().sum::<u8>() % 2;
+        cube
+    }
+
+    /// Writes the pieces as a facelet string, the reverse of [`CubieCube::from_facelets`].
+    fn to_facelets(self) -> String {
+        let mut faces: [usize; 54] = std::array::from_fn(|i| i / 9);
+        for (i, stickers) in CORNER_FACELETS.iter().enumerate() {
+            for (k, &face) in CORNER_COLORS[self.cp[i] as usize].iter().enumerate() {
+                faces[stickers[(self.co[i] as usize + k) % 3]] = face;
+            }
+        }
+        for (i, stickers) in EDGE_FACELETS.iter().enumerate() {
+            for (k, &face) in EDGE_COLORS[self.ep[i] as usize].iter().enumerate() {
+                faces[stickers[(self.eo[i] as usize + k) % 2]] = face;
+            }
+        }
+        faces.iter().map(|&face| FACE_LETTERS[face]).collect()
+    }
+
+    /// This cube followed by `other`.
+    pub(crate) fn multiply(&self, other: &CubieCube) -> CubieCube {
+        let mut cube = CubieCube::SOLVED;
+        for i in 0..8 {
+            let from = other.cp[i] as usize;
+            cube.cp[i] = self.cp[from];
+            cube.co[i] = (self.co[from] + other.co[i]) % 3;
+        }
+        for i in 0..12 {
+            let from = other.ep[i] as usize;
+            cube.ep[i] = self.ep[from];
+            cube.eo[i] = (self.eo[from] + other.eo[i]) % 2;
+        }
+        cube
+    }
+
+    /// 0 to 2186: how each corner but the last is twisted.
+    fn twist(&self) -> usize {
+        self.co[..7].iter().fold(0, |t, &o| 3 * t + o as usize)
+    }
+
+    /// 0 to 2047: how each edge but the last is flipped.
+    fn flip(&self) -> usize {
+        self.eo[..11].iter().fold(0, |f, &o| 2 * f + o as usize)
+    }
+
+    /// 0 to 494: which four positions hold the middle-layer edges, whatever their order.
+    fn slice(&self) -> usize {
+        let mut slice = 0;
+        let mut found = 0;
+        for j in (0..12).rev() {
+            if self.ep[j] >= 8 {
+                slice += binomial(11 - j, found + 1);
+                found += 1;
+            }
+        }
+        slice
+    }
+
+    /// 0 to 40319: the order of the corners.
+    fn corner_perm(&self) -> usize {
+        rank(&self.cp)
+    }
+
+    /// 0 to 40319: the order of the up and down layer edges, for cubes in G1.
+    fn ud_edge_perm(&self) -> usize {
+        rank(&self.ep[..8])
+    }
+
+    /// 0 to 23: the order of the middle-layer edges, for cubes in G1.
+    fn slice_perm(&self) -> usize {
+        rank(&self.ep[8..])
+    }
+}
+
+/// Whether `perm` takes an odd number of swaps to put in order.
+pub(crate) fn is_odd(perm: &[u8]) -> bool {
+    let inversions = (0..perm.len())
+        .flat_map(|i| (i + 1..perm.len()).map(move |j| (i, j)))
+        .filter(|&(i, j)| perm[i] > perm[j])
+        .count();
+    inversions % 2 == 1
+}
+
+fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    (0..k).fold(1, |c, i| c * (n - i) / (i + 1))
+}
+
+/// The position of `perm` in lexicographic order among the orderings of its elements, which
+/// must be distinct.
+fn rank(perm: &[u8]) -> usize {
+    perm.iter().enumerate().fold(0, |r, (i, &p)| {
+        let smaller_after = perm[i + 1..].iter().filter(|&&q| q < p).count();
+        r * (perm.len() - i) + smaller_after
+    })
+}
+
+/// How a coordinate changes under each move, indexed by `coordinate * MOVES + move`, found by
+/// walking every value the coordinate can reach from solved with `moves`.
+fn move_table(
+    basic: &[CubieCube; MOVES],
+    size: usize,
+    moves: &[usize],
+    coordinate: fn(&CubieCube) -> usize,
+) -> Vec<u16> {
+    let mut table = vec![u16::MAX; size * MOVES];
+    let mut seen = vec![false; size];
+    let mut queue = VecDeque::from([CubieCube::SOLVED]);
+    seen[coordinate(&CubieCube::SOLVED)] = true;
+    while let Some(cube) = queue.pop_front() {
+        let from = coordinate(&cube);
+        for &m in moves {
+            let next = cube.multiply(&basic[m]);
+            let to = coordinate(&next);
+            table[from * MOVES + m] = to as u16;
+            if !seen[to] {
+                seen[to] = true;
+                queue.push_back(next);
+            }
+        }
+    }
+    table
+}
+
+/// Exact distances from solved in the pair of coordinates `(a, b)`, indexed by `a * b_size + b`.
+fn pruning_table(a_moves: &[u16], b_moves: &[u16], b_size: usize, moves: &[usize]) -> Vec<u8> {
+    let a_size = a_moves.len() / MOVES;
+    let mut distance = vec![u8::MAX; a_size * b_size];
+    distance[0] = 0;
+    let mut queue = VecDeque::from([0]);
+    while let Some(index) = queue.pop_front() {
+        let (a, b) = (index / b_size, index % b_size);
+        for &m in moves {
+            let next = a_moves[a * MOVES + m] as usize * b_size + b_moves[b * MOVES + m] as usize;
+            if distance[next] == u8::MAX {
+                distance[next] = distance[index] + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    distance
+}
+
+/// Starts every file written by [`save_pruning_tables`], followed by [`TABLES_VERSION`].
+const TABLES_MAGIC: &[u8; 4] = b"RCKT";
+
+/// Bump whenever the tables or how they're written change, so older files are rebuilt instead
+/// of read.
+const TABLES_VERSION: u32 = 1;
+
+/// How many values each coordinate has, in the order the move tables are written.
+const MOVE_TABLE_SIZES: [usize; 6] = [2187, 2048, 495, 40320, 40320, 24];
+
+/// How many entries each pruning table has, in the order they're written.
+const PRUNING_TABLE_SIZES: [usize; 4] = [495 * 2187, 495 * 2048, 24 * 40320, 24 * 40320];
+
+struct Tables {
+    basic: [CubieCube; MOVES],
+    twist: Vec<u16>,
+    flip: Vec<u16>,
+    slice: Vec<u16>,
+    corner_perm: Vec<u16>,
+    ud_edge_perm: Vec<u16>,
+    slice_perm: Vec<u16>,
+    slice_twist: Vec<u8>,
+    slice_flip: Vec<u8>,
+    slice_perm_corner_perm: Vec<u8>,
+    slice_perm_ud_edge_perm: Vec<u8>,
+}
+
+impl Tables {
+    fn build() -> Tables {
+        let basic = basic_moves();
+        let all: Vec<usize> = (0..MOVES).collect();
+
+        let twist = move_table(&basic, 2187, &all, CubieCube::twist);
+        let flip = move_table(&basic, 2048, &all, CubieCube::flip);
+        let slice = move_table(&basic, 495, &all, CubieCube::slice);
+        let corner_perm = move_table(&basic, 40320, &PHASE_2_MOVES, CubieCube::corner_perm);
+        let ud_edge_perm = move_table(&basic, 40320, &PHASE_2_MOVES, CubieCube::ud_edge_perm);
+        let slice_perm = move_table(&basic, 24, &PHASE_2_MOVES, CubieCube::slice_perm);
+
+        Tables {
+            slice_twist: pruning_table(&slice, &twist, 2187, &all),
+            slice_flip: pruning_table(&slice, &flip, 2048, &all),
+            slice_perm_corner_perm: pruning_table(&slice_perm, &corner_perm, 40320, &PHASE_2_MOVES),
+            slice_perm_ud_edge_perm: pruning_table(
+                &slice_perm,
+                &ud_edge_perm,
+                40320,
+                &PHASE_2_MOVES,
+            ),
+            basic,
+            twist,
+            flip,
+            slice,
+            corner_perm,
+            ud_edge_perm,
+            slice_perm,
+        }
+    }
+
+    /// The tables as written by [`save_pruning_tables`]: the magic and version, then each move
+    /// table and each pruning table as a little-endian `u32` entry count followed by the
+    /// entries, also little-endian.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = TABLES_MAGIC.to_vec();
+        bytes.extend(TABLES_VERSION.to_le_bytes());
+        for table in self.move_tables() {
+            bytes.extend((table.len() as u32).to_le_bytes());
+            bytes.extend(table.iter().flat_map(|entry| entry.to_le_bytes()));
+        }
+        for table in self.pruning_tables() {
+            bytes.extend((table.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(table);
+        }
+        bytes
+    }
+
+    /// Reads tables written by [`Tables::to_bytes`], or `None` if `bytes` is from another
+    /// version or any table is the wrong size.
+    fn from_bytes(bytes: &[u8]) -> Option<Tables> {
+        fn take<'a>(bytes: &mut &'a [u8], n: usize) -> Option<&'a [u8]> {
+            if bytes.len() < n {
+                return None;
+            }
+            let (taken, rest) = bytes.split_at(n);
+            *bytes = rest;
+            Some(taken)
+        }
+        fn table<'a>(bytes: &mut &'a [u8], len: usize, entry_size: usize) -> Option<&'a [u8]> {
+            let count = u32::from_le_bytes(take(bytes, 4)?.try_into().unwrap());
+            if count as usize != len {
+                return None;
+            }
+            take(bytes, len * entry_size)
+        }
+
+        let mut bytes = bytes.strip_prefix(TABLES_MAGIC)?;
+        let version = u32::from_le_bytes(take(&mut bytes, 4)?.try_into().unwrap());
+        if version != TABLES_VERSION {
+            return None;
+        }
+        let mut move_tables = Vec::new();
+        for size in MOVE_TABLE_SIZES {
+            let entries = table(&mut bytes, size * MOVES, 2)?;
+            move_tables.push(
+                entries
+                    .chunks_exact(2)
+                    .map(|entry| u16::from_le_bytes([entry[0], entry[1]]))
+                    .collect(),
+            );
+        }
+        let mut pruning_tables = Vec::new();
+        for size in PRUNING_TABLE_SIZES {
+            pruning_tables.push(table(&mut bytes, size, 1)?.to_vec());
+        }
+        if !bytes.is_empty() {
+            return None;
+        }
+
+        let move_tables: [Vec<u16>; 6] = move_tables.try_into().unwrap();
+        let [twist, flip, slice, corner_perm, ud_edge_perm, slice_perm] = move_tables;
+        let pruning_tables: [Vec<u8>; 4] = pruning_tables.try_into().unwrap();
+        let [slice_twist, slice_flip, slice_perm_corner_perm, slice_perm_ud_edge_perm] =
+            pruning_tables;
+        Some(Tables {
+            basic: basic_moves(),
+            twist,
+            flip,
+            slice,
+            corner_perm,
+            ud_edge_perm,
+            slice_perm,
+            slice_twist,
+            slice_flip,
+            slice_perm_corner_perm,
+            slice_perm_ud_edge_perm,
+        })
+    }
+
+    fn move_tables(&self) -> [&[u16]; 6] {
+        [
+            &self.twist,
+            &self.flip,
+            &self.slice,
+            &self.corner_perm,
+            &self.ud_edge_perm,
+            &self.slice_perm,
+        ]
+    }
+
+    fn pruning_tables(&self) -> [&[u8]; 4] {
+        [
+            &self.slice_twist,
+            &self.slice_flip,
+            &self.slice_perm_corner_perm,
+            &self.slice_perm_ud_edge_perm,
+        ]
+    }
+
+    /// A lower bound on the phase 1 moves left.
+    fn phase_1_distance(&self, twist: usize, flip: usize, slice: usize) -> usize {
+        let by_twist = self.slice_twist[slice * 2187 + twist];
+        let by_flip = self.slice_flip[slice * 2048 + flip];
+        by_twist.max(by_flip) as usize
+    }
+
+    /// A lower bound on the phase 2 moves left.
+    fn phase_2_distance(
+        &self,
+        corner_perm: usize,
+        ud_edge_perm: usize,
+        slice_perm: usize,
+    ) -> usize {
+        let by_corners = self.slice_perm_corner_perm[slice_perm * 40320 + corner_perm];
+        let by_edges = self.slice_perm_ud_edge_perm[slice_perm * 40320 + ud_edge_perm];
+        by_corners.max(by_edges) as usize
+    }
+}
+
+/// Each move as pieces, read off a solved cube turned by `rotate_face` so the move tables
+/// can't disagree with how the rest of the crate turns cubes.
+pub(crate) fn basic_moves() -> [CubieCube; MOVES] {
+    std::array::from_fn(|m| {
+        let mut rc = RubiksCube::new(3);
+        rotate_face(&mut rc, FACES[m / 3], movement(m), 0);
+        CubieCube::from_facelets(&rc.to_facelet_string()).expect("a turned solved cube is valid")
+    })
+}
+
+static TABLES: OnceLock<Tables> = OnceLock::new();
+
+fn tables() -> &'static Tables {
+    TABLES.get_or_init(Tables::build)
+}
+
+/// Writes the tables [`RubiksCube::solve_kociemba`] searches with to `path`, building them
+/// first if they haven't been yet, so a later process can skip building them with
+/// [`load_pruning_tables`].
+pub fn save_pruning_tables(path: impl AsRef<Path>) -> io::Result<()> {
+    std::fs::write(path, tables().to_bytes())
+}
+
+/// Reads tables written by [`save_pruning_tables`] for [`RubiksCube::solve_kociemba`] to use,
+/// and returns whether they could be used. A file from a different version of the tables, or
+/// with a table of the wrong size, is ignored and the tables are built from scratch instead;
+/// the file is left as it is. Tables already built or loaded in this process are kept either
+/// way. Fails only if `path` can't be read.
+pub fn load_pruning_tables(path: impl AsRef<Path>) -> io::Result<bool> {
+    let bytes = std::fs::read(path)?;
+    match Tables::from_bytes(&bytes) {
+        Some(loaded) => {
+            let _ = TABLES.set(loaded);
+            Ok(true)
+        }
+        None => {
+            tables();
+            Ok(false)
+        }
+    }
+}
+
+fn movement(m: usize) -> Movement {
+    [
+        Movement::Clockwise,
+        Movement::Half,
+        Movement::CounterClockwise,
+    ][m % 3]
+}
+
+/// Whether `m` may follow `last`: never the same face twice, and opposite faces, which
+/// commute, only in one order.
+fn allowed_after(m: usize, last: Option<usize>) -> bool {
+    match last {
+        Some(last) => {
+            let (face, last_face) = (m / 3, last / 3);
+            face != last_face && face + 3 != last_face
+        }
+        None => true,
+    }
+}
+
+struct Search<'a> {
+    tables: &'a Tables,
+    start: CubieCube,
+    max_depth: usize,
+    moves: Vec<usize>,
+    best: Option<Vec<usize>>,
+}
+
+impl Search<'_> {
+    fn phase_1(&mut self, twist: usize, flip: usize, slice: usize, depth_left: usize) {
+        let t = self.tables;
+        if depth_left == 0 {
+            if twist == 0 && flip == 0 && slice == 0 {
+                // A solution ending in a G1 move would have reached G1 a move earlier.
+                let ends_in_g1 = self.moves.last().is_some_and(|m| PHASE_2_MOVES.contains(m));
+                if !ends_in_g1 {
+                    self.start_phase_2();
+                }
+            }
+            return;
+        }
+        for m in 0..MOVES {
+            if !allowed_after(m, self.moves.last().copied()) {
+                continue;
+            }
+            let twist = t.twist[twist * MOVES + m] as usize;
+            let flip = t.flip[flip * MOVES + m] as usize;
+            let slice = t.slice[slice * MOVES + m] as usize;
+            if t.phase_1_distance(twist, flip, slice) < depth_left {
+                self.moves.push(m);
+                self.phase_1(twist, flip, slice, depth_left - 1);
+                self.moves.pop();
+            }
+        }
+    }
+
+    fn start_phase_2(&mut self) {
+        let cube = self
+            .moves
+            .iter()
+            .fold(self.start, |cube, &m| cube.multiply(&self.tables.basic[m]));
+        let limit = match &self.best {
+            Some(best) => best.len() - 1,
+            None => self.max_depth,
+        };
+        let Some(depth_left) = limit.checked_sub(self.moves.len()) else {
+            return;
+        };
+
+        let (cp, ep, sp) = (cube.corner_perm(), cube.ud_edge_perm(), cube.slice_perm());
+        let phase_1_len = self.moves.len();
+        for depth in self.tables.phase_2_distance(cp, ep, sp)..=depth_left.min(MAX_PHASE_2_DEPTH) {
+            if self.phase_2(cp, ep, sp, depth) {
+                self.best = Some(self.moves.clone());
+                self.moves.truncate(phase_1_len);
+                return;
+            }
+        }
+    }
+
+    fn phase_2(&mut self, cp: usize, ep: usize, sp: usize, depth_left: usize) -> bool {
+        let t = self.tables;
+        if depth_left == 0 {
+            return cp == 0 && ep == 0 && sp == 0;
+        }
+        for m in PHASE_2_MOVES {
+            if !allowed_after(m, self.moves.last().copied()) {
+                continue;
+            }
+            let cp = t.corner_perm[cp * MOVES + m] as usize;
+            let ep = t.ud_edge_perm[ep * MOVES + m] as usize;
+            let sp = t.slice_perm[sp * MOVES + m] as usize;
+            if t.phase_2_distance(cp, ep, sp) < depth_left {
+                self.moves.push(m);
+                if self.phase_2(cp, ep, sp, depth_left - 1) {
+                    return true;
+                }
+                self.moves.pop();
+            }
+        }
+        false
+    }
+}
+
+impl RubiksCube {
+    /// A 3x3x3 picked uniformly from every solvable state, the way official scramblers pick
+    /// them. Random turns take a long time to get anywhere near this even a spread; see
+    /// [`random_state_scramble`](crate::random_state_scramble) for moves that reach the state.
+    pub fn random_state(rng: &mut impl Rng) -> RubiksCube {
+        RubiksCube::from_facelet_string(&CubieCube::random(rng).to_facelets())
+            .expect("pieces always make a complete facelet string")
+    }
+
+    /// Solves a 3x3x3 with Kociemba's two-phase algorithm, in at most `max_depth` outer layer
+    /// turns. Solutions usually come out at around 20 to 24 moves, well short of what
+    /// [`RubiksCube::solve_beginner`] finds, but they aren't guaranteed to be optimal: once a
+    /// solution turns up, one more phase 1 depth is searched for a shorter one, then the
+    /// shortest found is returned.
+    ///
+    /// The move and pruning tables take a moment to build on the first call and are shared by
+    /// every call after it. Returns `None` for other sizes, for cubes that fail
+    /// [`RubiksCube::is_valid`], and if no solution within `max_depth` turns up.
+    pub fn solve_kociemba(&self, max_depth: usize) -> Option<Vec<Move>> {
+        if self.size != 3 || self.is_valid().is_err() {
+            return None;
+        }
+        let start = CubieCube::from_facelets(&self.to_facelet_string())?;
+        let tables = tables();
+
+        let mut search = Search {
+            tables,
+            start,
+            max_depth,
+            moves: Vec::new(),
+            best: None,
+        };
+        let (twist, flip, slice) = (start.twist(), start.flip(), start.slice());
+        let mut first_found = None;
+        for depth in tables.phase_1_distance(twist, flip, slice)..=max_depth {
+            if let Some(best) = &search.best {
+                let first = *first_found.get_or_insert(depth - 1);
+                if depth >= best.len() || depth > first + EXTRA_PHASE_1_DEPTH {
+                    break;
+                }
+            }
+            search.phase_1(twist, flip, slice, depth);
+        }
+
+        let moves: Vec<_> = search
+            .best?
+            .into_iter()
+            .map(|m| Move {
+                face: FACES[m / 3],
+                movement: movement(m),
+                depth: 0,
+            })
+            .collect();
+        Some(simplify(&moves))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn pieces_follow_the_stickers() {
+        use crate::kociemba::{tables, CubieCube, FACES, MOVES};
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(4);
+        let mut rc = crate::RubiksCube::new(3);
+        let mut cube = CubieCube::SOLVED;
+        for _ in 0..100 {
+            let m = rng.gen_range(0..MOVES);
+            crate::rotate_face(&mut rc, FACES[m / 3], crate::kociemba::movement(m), 0);
+            cube = cube.multiply(&tables().basic[m]);
+            assert!(CubieCube::from_facelets(&rc.to_facelet_string()) == Some(cube));
+        }
+    }
+
+    #[test]
+    fn kociemba_solves_scrambles() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(21);
+        for _ in 0..10 {
+            let mut rc = crate::RubiksCube::new(3);
+            rc.apply_moves(&crate::scramble(3, 40, &mut rng));
+
+            let solution = rc.solve_kociemba(30).unwrap();
+            assert!(solution.len() <= 24, "{}", solution.len());
+            assert!(solution.iter().all(|m| m.depth == 0));
+            rc.apply_moves(&solution);
+            assert!(rc.is_solved(), "{rc}");
+        }
+
+        let solved = crate::RubiksCube::new(3);
+        assert_eq!(solved.solve_kociemba(20), Some(vec![]));
+        assert_eq!(crate::RubiksCube::new(4).solve_kociemba(20), None);
+
+        let mut superflip = crate::RubiksCube::new(3);
+        crate::patterns::superflip(&mut superflip).unwrap();
+        assert_eq!(superflip.solve_kociemba(5), None);
+    }
+
+    #[test]
+    fn pruning_tables_round_trip_through_a_file() {
+        use crate::kociemba::{tables, Tables, TABLES_VERSION};
+
+        let path = std::env::temp_dir().join(format!("rcs-tables-{}.bin", std::process::id()));
+        crate::save_pruning_tables(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(bytes, tables().to_bytes());
+        assert!(Tables::from_bytes(&bytes).unwrap().to_bytes() == bytes);
+        assert!(crate::load_pruning_tables(&path).unwrap());
+
+        let mut old_version = bytes.clone();
+        old_version[4..8].copy_from_slice(&(TABLES_VERSION + 1).to_le_bytes());
+        let mut wrong_size = bytes.clone();
+        wrong_size[8] ^= 1;
+        for bad in [
+            old_version,
+            wrong_size,
+            bytes[..bytes.len() - 1].to_vec(),
+            vec![],
+        ] {
+            assert!(Tables::from_bytes(&bad).is_none());
+            std::fs::write(&path, bad).unwrap();
+            assert!(!crate::load_pruning_tables(&path).unwrap());
+        }
+
+        std::fs::remove_file(&path).unwrap();
+        assert!(crate::load_pruning_tables(&path).is_err());
+    }
+
+    #[test]
+    fn random_pieces_round_trip_through_facelets() {
+        use crate::kociemba::{is_odd, CubieCube};
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(558);
+        let mut odd = 0;
+        for _ in 0..1000 {
+            let cube = CubieCube::random(&mut rng);
+            assert!(CubieCube::from_facelets(&cube.to_facelets()) == Some(cube));
+            assert_eq!(is_odd(&cube.cp), is_odd(&cube.ep));
+            odd += is_odd(&cube.cp) as usize;
+        }
+        // Half of all states have their corners in an odd order.
+        assert!((400..600).contains(&odd), "{odd} odd of 1000");
+    }
+}
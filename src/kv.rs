@@ -0,0 +1,181 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::{Color, Face, RubiksCube};
+
+const FACE_KEYS: [(Face, &str); 6] = [
+    (Face::Up, "up"),
+    (Face::Left, "left"),
+    (Face::Front, "front"),
+    (Face::Right, "right"),
+    (Face::Back, "back"),
+    (Face::Down, "down"),
+];
+
+/// Why [`RubiksCube::from_kv`] couldn't read its input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line that isn't blank, a `#` comment or a `key = value` pair.
+    InvalidLine {
+        line: usize,
+        content: String,
+    },
+    /// A key other than `size` or a face name.
+    UnknownKey(String),
+    MissingKey(String),
+    InvalidSize(String),
+    /// A face's stickers have the wrong count or an unknown color letter.
+    InvalidFace {
+        key: String,
+        reason: String,
+    },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::InvalidLine { line, content } => {
+                write!(fmt, "line {line} isn't a `key = value` pair: {content:?}")
+            }
+            ParseError::UnknownKey(key) => write!(fmt, "unknown key {key:?}"),
+            ParseError::MissingKey(key) => write!(fmt, "missing key {key:?}"),
+            ParseError::InvalidSize(size) => write!(fmt, "invalid size {size:?}"),
+            ParseError::InvalidFace { key, reason } => write!(fmt, "invalid {key} face: {reason}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+impl RubiksCube {
+    /// Writes the cube as `key = value` lines: `size = N`, then one line per face such as
+    /// `up = "YYYYYYYYY"`, so the letters come in the canonical [`RubiksCube::stickers`] order.
+    pub fn to_kv(&self) -> String {
+        let mut kv = format!("size = {}\n", self.size);
+        for (face, key) in FACE_KEYS {
+            let stickers: String = self[face].iter().map(|c| c.to_char()).collect();
+            kv.push_str(&format!("{key} = \"{stickers}\"\n"));
+        }
+        kv
+    }
+
+    /// Reads a cube written by [`RubiksCube::to_kv`]. Keys may come in any order, values may be
+    /// quoted or not, and blank lines and `#` comments are skipped.
+    pub fn from_kv(s: &str) -> Result<RubiksCube, ParseError> {
+        let mut size = None;
+        let mut faces: [Option<String>; 6] = Default::default();
+
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| ParseError::InvalidLine {
+                    line: i + 1,
+                    content: line.to_string(),
+                })?;
+            let key = key.trim();
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(value);
+
+            if key == "size" {
+                size = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| ParseError::InvalidSize(value.to_string()))?,
+                );
+            } else {
+                let (face, _) = FACE_KEYS
+                    .iter()
+                    .find(|(_, k)| *k == key)
+                    .ok_or_else(|| ParseError::UnknownKey(key.to_string()))?;
+                faces[*face as usize] = Some(value.to_string());
+            }
+        }
+
+        let size = size.ok_or_else(|| ParseError::MissingKey("size".to_string()))?;
+        let mut rc = RubiksCube::new(size);
+        for (face, key) in FACE_KEYS {
+            let stickers = faces[face as usize]
+                .as_ref()
+                .ok_or_else(|| ParseError::MissingKey(key.to_string()))?;
+            let colors = stickers
+                .chars()
+                .map(|c| {
+                    Color::from_char(c).ok_or_else(|| ParseError::InvalidFace {
+                        key: key.to_string(),
+                        reason: format!("unknown color {c:?}"),
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            if colors.len() != size * size {
+                return Err(ParseError::InvalidFace {
+                    key: key.to_string(),
+                    reason: format!("expected {} stickers, got {}", size * size, colors.len()),
+                });
+            }
+            rc[face].copy_from_slice(&colors);
+        }
+        Ok(rc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn kv_round_trip() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(4);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        crate::rotate_face(&mut rc, Up, CounterClockwise, 1);
+
+        let kv = rc.to_kv();
+        assert!(kv.starts_with("size = 4\nup = \""));
+        assert_eq!(crate::RubiksCube::from_kv(&kv), Ok(rc));
+
+        let edited = "# a solved 2x2\n\ndown=WWWW\nsize = 2\nup = YYYY\nleft = OOOO\n\
+                      front = BBBB\nright = RRRR\nback = GGGG\n";
+        assert_eq!(
+            crate::RubiksCube::from_kv(edited),
+            Ok(crate::RubiksCube::new(2))
+        );
+    }
+
+    #[test]
+    fn kv_reports_errors() {
+        use crate::ParseError;
+
+        let kv = crate::RubiksCube::new(2).to_kv();
+        let without_back: String = kv
+            .lines()
+            .filter(|l| !l.starts_with("back"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            crate::RubiksCube::from_kv(&without_back),
+            Err(ParseError::MissingKey("back".to_string()))
+        );
+
+        assert!(matches!(
+            crate::RubiksCube::from_kv(&kv.replace("YYYY", "YYXY")),
+            Err(ParseError::InvalidFace { .. })
+        ));
+        assert!(matches!(
+            crate::RubiksCube::from_kv(&kv.replace("size = 2", "size = 3")),
+            Err(ParseError::InvalidFace { .. })
+        ));
+        assert!(matches!(
+            crate::RubiksCube::from_kv("size 2"),
+            Err(ParseError::InvalidLine { line: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,3358 @@
+//! Turning, printing and solving Rubik's cubes of any size.
+//!
+//! Without the default `std` feature the crate is `no_std`, needing only `alloc`: cubes can
+//! still be built, turned, compared, parsed and drawn as text or SVG, but solvers, scrambles,
+//! validation, file I/O and terminal colors are left out.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display, Formatter};
+use core::ops::{Index, IndexMut, Range};
+
+#[cfg(any(test, feature = "proptest"))]
+mod arbitrary;
+#[cfg(feature = "std")]
+mod beginner;
+#[cfg(feature = "std")]
+mod f2l;
+mod fixed_size;
+#[cfg(feature = "std")]
+mod kociemba;
+mod kv;
+mod notation;
+mod packed;
+#[cfg(feature = "parallel")]
+mod parallel;
+pub mod patterns;
+#[cfg(feature = "image")]
+mod png;
+#[cfg(feature = "std")]
+mod replay;
+#[cfg(feature = "std")]
+mod scan;
+mod scheme;
+#[cfg(feature = "std")]
+mod scramble;
+mod sequence;
+#[cfg(feature = "std")]
+mod solver;
+mod supercube;
+mod svg;
+#[cfg(feature = "std")]
+mod thistlethwaite;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "std")]
+mod validation;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+#[cfg(feature = "proptest")]
+pub use arbitrary::MAX_ARBITRARY_SIZE;
+#[cfg(feature = "std")]
+pub use f2l::{solve_f2l_pair, F2lSlot};
+pub use fixed_size::Cube;
+#[cfg(feature = "std")]
+pub use kociemba::{load_pruning_tables, save_pruning_tables};
+pub use kv::ParseError;
+pub use notation::sequence_to_string;
+#[cfg(feature = "parallel")]
+pub use parallel::PARALLEL_THRESHOLD;
+#[cfg(feature = "std")]
+pub use replay::{replay_file, save_moves, ReplayError};
+#[cfg(feature = "std")]
+pub use scan::ScanError;
+pub use scheme::ColorScheme;
+#[cfg(feature = "std")]
+pub use scramble::{
+    effective_scramble_depth, random_state_scramble, scramble, scramble_entropy,
+    scramble_last_layer_only, scramble_to_entropy, MAX_ENTROPY_SCRAMBLE_MOVES,
+};
+pub use sequence::{
+    commutator, concat_optimized, conjugate, invert, metric_count, optimize_sequence, simplify,
+    Metric,
+};
+#[cfg(feature = "std")]
+pub use solver::{
+    compare_solvers, count_states_2x2, count_states_2x2_within, optimal_distance_2x2, SolveError,
+    SolveObserver, SolverComparison, SolverStats,
+};
+#[cfg(feature = "std")]
+pub use validation::{suggest_scan_fix, ValidationError};
+#[cfg(feature = "wasm")]
+pub use wasm::WasmCube;
+
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "SerializedCube"))]
+pub struct RubiksCube {
+    size: usize,
+    faces: [Vec<Color>; 6],
+    /// The moves made with [`RubiksCube::apply_move`] that [`RubiksCube::undo`] can take back,
+    /// or `None` while recording is off. Not part of the cube's state, so it's left out of
+    /// comparisons, hashing and serialization.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    history: Option<Vec<Move>>,
+    /// For a supercube, which way each sticker points, in quarter turns clockwise from
+    /// upright; `None` for an ordinary cube. See [`RubiksCube::new_supercube`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    orientations: Option<[Vec<u8>; 6]>,
+}
+
+impl PartialEq for RubiksCube {
+    fn eq(&self, other: &Self) -> bool {
+        self.size == other.size
+            && self.faces == other.faces
+            && self.orientations == other.orientations
+    }
+}
+
+impl Eq for RubiksCube {}
+
+impl core::hash::Hash for RubiksCube {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.size.hash(state);
+        self.faces.hash(state);
+        self.orientations.hash(state);
+    }
+}
+
+impl RubiksCube {
+    pub fn new(size: usize) -> RubiksCube {
+        RubiksCube::new_with_scheme(size, &ColorScheme::default())
+    }
+
+    /// A solved cube of `size`. The same as [`RubiksCube::new`], for when the name reads better.
+    pub fn solved(size: usize) -> RubiksCube {
+        RubiksCube::new(size)
+    }
+
+    /// Builds a cube from its faces in [`Face`] order, each given as rows from top to bottom as
+    /// laid out in the net. Fails unless every face is `size` rows of `size` stickers.
+    pub fn from_faces(size: usize, faces: [Vec<Vec<Color>>; 6]) -> Result<RubiksCube, String> {
+        for (i, face) in faces.iter().enumerate() {
+            let face_name: Face = num_traits::FromPrimitive::from_usize(i).unwrap();
+            if face.len() != size {
+                return Err(format!(
+                    "{face_name:?} face has {} rows but a {size}x{size}x{size} cube needs {size}",
+                    face.len()
+                ));
+            }
+            if let Some((row, stickers)) = face.iter().enumerate().find(|(_, r)| r.len() != size) {
+                return Err(format!(
+                    "row {row} of the {face_name:?} face has {} stickers but a \
+                     {size}x{size}x{size} cube needs {size}",
+                    stickers.len()
+                ));
+            }
+        }
+        Ok(RubiksCube {
+            size,
+            faces: faces.map(|face| face.concat()),
+            history: Some(Vec::new()),
+            orientations: None,
+        })
+    }
+
+    /// Reflects the cube in the plane through its middle that `axis` runs through, leaving its
+    /// mirror image: for [`Axis::X`] the left and right faces swap places and every other face
+    /// is flipped left to right, and likewise for the other axes. Turns made on the mirror image
+    /// are mirrored too, so `R` on the original matches `L'` on its reflection in x. Mirroring
+    /// twice in the same axis gives back the original cube.
+    pub fn mirror(&mut self, axis: Axis) {
+        let s = self.size.saturating_sub(1);
+        let reflect = |(x, y, z): (usize, usize, usize)| match axis {
+            Axis::X => (s - x, y, z),
+            Axis::Y => (x, s - y, z),
+            Axis::Z => (x, y, s - z),
+        };
+        let reflect_face = |face: Face| {
+            if face.axis() == axis {
+                face.opposite()
+            } else {
+                face
+            }
+        };
+
+        let mut stickers = alloc::collections::BTreeMap::new();
+        for (face, row, col, _) in self.stickers() {
+            stickers.insert(
+                (face as usize, sticker_position(self.size, face, row, col)),
+                (row, col),
+            );
+        }
+        let original = self.clone();
+        for (face, row, col, color) in original.stickers() {
+            let image = reflect_face(face);
+            let (row, col) = stickers[&(
+                image as usize,
+                reflect(sticker_position(self.size, face, row, col)),
+            )];
+            self.set(image, row, col, color);
+        }
+    }
+
+    /// Undoes the last move recorded by [`RubiksCube::apply_move`], returning it, or `None` if
+    /// there is nothing to undo.
+    pub fn undo(&mut self) -> Option<Move> {
+        let m = self.history.as_mut()?.pop()?;
+        rotate_face(self, m.face, m.movement.inverse(), m.depth);
+        Some(m)
+    }
+
+    /// Undoes every recorded move, taking the cube back to how it was when recording started:
+    /// solved, for a cube from [`RubiksCube::new`] that has only been turned with
+    /// [`RubiksCube::apply_move`].
+    pub fn reset(&mut self) {
+        while self.undo().is_some() {}
+    }
+
+    /// The moves [`RubiksCube::undo`] would take back, oldest first.
+    pub fn history(&self) -> &[Move] {
+        self.history.as_deref().unwrap_or_default()
+    }
+
+    /// Turns recording of moves for [`RubiksCube::undo`] on or off. It starts on; turning it off
+    /// forgets the moves recorded so far and saves the bookkeeping in tight loops that clone and
+    /// turn cubes over and over. Turns made with [`rotate_face`] and the other free functions
+    /// are never recorded.
+    pub fn set_history_enabled(&mut self, enabled: bool) {
+        match (enabled, &self.history) {
+            (true, None) => self.history = Some(Vec::new()),
+            (false, Some(_)) => self.history = None,
+            _ => {}
+        }
+    }
+
+    /// Turns the whole cube around `axis`, the same way as turning every layer of the face the
+    /// axis follows (R for x, U for y, F for z).
+    pub fn rotate_cube(&mut self, axis: Axis, movement: Movement) {
+        for depth in 0..self.size {
+            rotate_face(self, axis.faces().0, movement, depth);
+        }
+    }
+
+    /// Makes `m`, the same as [`rotate_face`], and records it for [`RubiksCube::undo`] if
+    /// recording is on. Panics if `m` is deeper than the cube.
+    pub fn apply_move(&mut self, m: Move) {
+        rotate_face(self, m.face, m.movement, m.depth);
+        if let Some(history) = &mut self.history {
+            history.push(m);
+        }
+    }
+
+    /// Undoes `m` in one turn: the same layer turned the other way, recorded like any other
+    /// move. Panics if `m` is deeper than the cube.
+    pub fn apply_inverse_move(&mut self, m: Move) {
+        self.apply_move(Move {
+            movement: m.movement.inverse(),
+            ..m
+        });
+    }
+
+    /// Makes each of `moves` in order. Panics if any of them is deeper than the cube.
+    pub fn apply_moves(&mut self, moves: &[Move]) {
+        for &m in moves {
+            self.apply_move(m);
+        }
+    }
+
+    /// Undoes `moves` in place: each one turned the other way, last first, as with
+    /// [`RubiksCube::apply_inverse_move`]. The same as applying the [`invert`]ed sequence
+    /// without building it. Panics if any of the moves is deeper than the cube.
+    pub fn apply_reversed(&mut self, moves: &[Move]) {
+        for &m in moves.iter().rev() {
+            self.apply_inverse_move(m);
+        }
+    }
+
+    /// Makes each of `moves` in order like [`RubiksCube::apply_moves`], calling `on_each` after
+    /// every one with the cube as it now is and the move just made, so animations and logs can
+    /// follow along. Panics if any of the moves is deeper than the cube.
+    pub fn apply_moves_with(&mut self, moves: &[Move], mut on_each: impl FnMut(&RubiksCube, Move)) {
+        for &m in moves {
+            self.apply_move(m);
+            on_each(self, m);
+        }
+    }
+
+    /// Renders the same net as `Display`, but with one plain letter per sticker and no terminal
+    /// escapes, for logs, files and diffs.
+    pub fn to_ascii_net(&self) -> String {
+        self.to_string_with_glyphs(&[
+            (Color::White, "W"),
+            (Color::Yellow, "Y"),
+            (Color::Red, "R"),
+            (Color::Orange, "O"),
+            (Color::Blue, "B"),
+            (Color::Green, "G"),
+        ])
+    }
+
+    /// Renders the same net as `Display`, drawing each sticker as the glyph given for its color.
+    /// Glyphs with fewer chars than the longest one are padded with spaces so the faces line up,
+    /// and colors missing from `glyphs` are drawn as `?`.
+    pub fn to_string_with_glyphs(&self, glyphs: &[(Color, &str); 6]) -> String {
+        let glyph = |color: Color| {
+            glyphs
+                .iter()
+                .find(|(c, _)| *c == color)
+                .map_or("?", |(_, glyph)| glyph)
+        };
+        let width = glyphs
+            .iter()
+            .map(|(_, glyph)| glyph.chars().count())
+            .max()
+            .unwrap_or(0);
+        let push_row = |net: &mut String, row: &[Color]| {
+            for &color in row {
+                net.push_str(&format!("{:width$}", glyph(color)));
+            }
+        };
+
+        let mut net = String::new();
+        let leading_spaces = " ".repeat(self.size * width);
+
+        for row in self.faces[0].chunks(self.size) {
+            net.push_str(&leading_spaces);
+            push_row(&mut net, row);
+            net.push('\n');
+        }
+
+        for row in 0..self.size {
+            for face in &self.faces[1..5] {
+                push_row(&mut net, &face[row * self.size..(row + 1) * self.size]);
+            }
+            net.push('\n');
+        }
+
+        for row in self.faces[5].chunks(self.size) {
+            net.push_str(&leading_spaces);
+            push_row(&mut net, row);
+            net.push('\n');
+        }
+
+        net
+    }
+
+    /// Applies a scramble written in WCA notation, such as `R U2 F' D2 Lw` or `3R 3Rw'` on big
+    /// cubes. Moves may be separated by any whitespace. Nothing is applied if any move is
+    /// invalid for this cube.
+    pub fn apply_wca(&mut self, scramble: &str) -> Result<(), String> {
+        let tokens = scramble
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<Vec<MoveToken>, _>>()?;
+        for &token in &tokens {
+            match token {
+                MoveToken::Turn(face, _, depth) if depth >= self.size => {
+                    return Err(format!(
+                        "can't turn layer {} of {face:?} on a {1}x{1}x{1} cube",
+                        depth + 1,
+                        self.size
+                    ));
+                }
+                MoveToken::Wide(face, _, layers) if layers > self.size => {
+                    return Err(format!(
+                        "can't turn {layers} layers of {face:?} on a {0}x{0}x{0} cube",
+                        self.size
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        for token in tokens {
+            match token {
+                MoveToken::Turn(face, movement, depth) => rotate_face(self, face, movement, depth),
+                MoveToken::Wide(face, movement, layers) => {
+                    rotate_wide(self, face, movement, layers)?
+                }
+                MoveToken::Rotation(axis, movement) => self.rotate_cube(axis, movement),
+            }
+        }
+        Ok(())
+    }
+
+    /// The color `face` is being solved to: its center sticker on odd cubes, or the most common
+    /// color on the face for even cubes, which have no single center.
+    ///
+    /// When colors tie for most common, the one `face` has on a solved cube wins if it's among
+    /// them, and otherwise the first in [`Color`]'s declaration order (white, yellow, red,
+    /// orange, blue, green). A cube with no stickers gives the solved color.
+    pub fn center_color(&self, face: Face) -> Color {
+        let stickers = &self[face];
+        if !self.size.is_multiple_of(2) {
+            return stickers[(self.size / 2) * self.size + self.size / 2];
+        }
+
+        let mut counts = [0usize; 6];
+        for &color in stickers {
+            counts[color as usize] += 1;
+        }
+        let home = solved_color(face);
+        COLORS
+            .into_iter()
+            .min_by_key(|&color| {
+                (
+                    core::cmp::Reverse(counts[color as usize]),
+                    color != home,
+                    color as usize,
+                )
+            })
+            .expect("there are six colors")
+    }
+
+    /// How many stickers match their face's [`center_color`](RubiksCube::center_color).
+    pub fn solved_sticker_count(&self) -> usize {
+        self.into_iter()
+            .map(|(face, stickers)| {
+                let color = self.center_color(face);
+                stickers.iter().filter(|&&c| c == color).count()
+            })
+            .sum()
+    }
+
+    /// A rough guess at how many turns are left to solve the cube: the stickers that don't
+    /// match their face (see [`RubiksCube::solved_sticker_count`]) divided by the `4 * size`
+    /// stickers a single layer turn carries from one face to another. It's quick and goes down
+    /// as the cube gets closer to solved, but it's neither a lower nor an upper bound on the
+    /// real distance.
+    pub fn estimated_moves_remaining(&self) -> usize {
+        let misplaced = 6 * self.size * self.size - self.solved_sticker_count();
+        misplaced.div_ceil(4 * self.size.max(1))
+    }
+
+    /// The fraction of each face's stickers that match the face's
+    /// [`center_color`](RubiksCube::center_color), in [`Face`] order.
+    pub fn face_progress(&self) -> [f32; 6] {
+        let count = (self.size * self.size) as f32;
+        let mut progress = [0.0; 6];
+        for (progress, (face, stickers)) in progress.iter_mut().zip(self) {
+            let color = self.center_color(face);
+            *progress = stickers.iter().filter(|&&c| c == color).count() as f32 / count;
+        }
+        progress
+    }
+
+    /// Collapses a reduced big cube (solved centers, paired edges) into the 3x3x3 it behaves
+    /// as, keeping its corners, one sticker from each edge strip and its center color. Returns
+    /// `None` for cubes smaller than 3x3x3 or that aren't reduced.
+    pub fn project_to_3x3(&self) -> Option<RubiksCube> {
+        let n = self.size;
+        if n < 3 {
+            return None;
+        }
+
+        for face in &self.faces {
+            let sticker = |row: usize, col: usize| face[row * n + col];
+            let inner = 1..n - 1;
+            let centers_solved = all_same(
+                inner
+                    .clone()
+                    .flat_map(|row| inner.clone().map(move |col| sticker(row, col))),
+            );
+            let edges_paired = all_same(inner.clone().map(|i| sticker(0, i)))
+                && all_same(inner.clone().map(|i| sticker(n - 1, i)))
+                && all_same(inner.clone().map(|i| sticker(i, 0)))
+                && all_same(inner.clone().map(|i| sticker(i, n - 1)));
+            if !centers_solved || !edges_paired {
+                return None;
+            }
+        }
+
+        let layer = |i: usize| if i == 2 { n - 1 } else { i };
+        let mut projected = RubiksCube::new(3);
+        for (small, big) in projected.faces.iter_mut().zip(&self.faces) {
+            for row in 0..3 {
+                for col in 0..3 {
+                    small[row * 3 + col] = big[layer(row) * n + layer(col)];
+                }
+            }
+        }
+        Some(projected)
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Checks whether `other` is this cube turned as a whole, by trying all 24 ways of holding
+    /// it. Cubes of different sizes are never equal.
+    pub fn equals_ignoring_orientation(&self, other: &RubiksCube) -> bool {
+        if self.size != other.size {
+            return false;
+        }
+        let mut held = self.clone();
+        // Bring each face to the top in turn (four around x, then z and z'), and try each of
+        // the four turns around y with it there.
+        let tops = [Axis::X, Axis::X, Axis::X, Axis::X, Axis::Z, Axis::Z];
+        for (i, axis) in tops.into_iter().enumerate() {
+            let movement = if i == 5 {
+                Movement::Half
+            } else {
+                Movement::Clockwise
+            };
+            held.rotate_cube(axis, movement);
+            for _ in 0..4 {
+                held.rotate_cube(Axis::Y, Movement::Clockwise);
+                if held == *other {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Whether every face is a single color. A [supercube](RubiksCube::new_supercube) also
+    /// needs its centers pointing the way they do on a solved one.
+    ///
+    /// This runs in solvers' inner loops, so it stops at the first sticker that differs from
+    /// the first of its face; `benches/is_solved.rs` times it against scanning every sticker.
+    pub fn is_solved(&self) -> bool {
+        self.faces.iter().all(|face| {
+            face.split_first()
+                .is_none_or(|(first, rest)| rest.iter().all(|color| color == first))
+        }) && self.orientations_solved()
+    }
+
+    /// The faces whose stickers are all one color, in [`Face`] order. Any color counts, so a
+    /// face can be complete even if it isn't the color it has on a solved cube.
+    pub fn solved_faces(&self) -> Vec<Face> {
+        self.faces
+            .iter()
+            .enumerate()
+            .filter(|(_, face)| all_same(face.iter().copied()))
+            .map(|(i, _)| num_traits::FromPrimitive::from_usize(i).unwrap())
+            .collect()
+    }
+
+    /// Checks whether the cube is solved apart from the outer layers of `faces`. Those faces are
+    /// ignored completely, along with the edge rows of their neighbours that turn with them.
+    pub fn is_solved_except(&self, faces: &[Face]) -> bool {
+        let mut ignored = [(); 6].map(|_| vec![false; self.size * self.size]);
+        for &face in faces {
+            ignored[face as usize].fill(true);
+            for side in get_sides(face) {
+                for i in 0..self.size {
+                    let (row, col) =
+                        position_based_off_corner_and_move_count(side.1, i, self.size, 0);
+                    ignored[side.0 as usize][row * self.size + col] = true;
+                }
+            }
+        }
+
+        self.faces.iter().zip(&ignored).all(|(face, ignored)| {
+            let mut counted = face.iter().zip(ignored).filter(|(_, &i)| !i);
+            match counted.next() {
+                Some((&first, _)) => counted.all(|(&color, _)| color == first),
+                None => true,
+            }
+        })
+    }
+
+    /// The color of the sticker at `row`, `col` of `face`, with rows and columns counted from
+    /// the top left of the face as laid out in the net.
+    ///
+    /// Panics if `row` or `col` is off the face.
+    pub fn get(&self, face: Face, row: usize, col: usize) -> Color {
+        self.check_sticker(face, row, col);
+        self[face][row * self.size + col]
+    }
+
+    /// Recolors the sticker at `row`, `col` of `face`. See [`RubiksCube::get`].
+    pub fn set(&mut self, face: Face, row: usize, col: usize, color: Color) {
+        self.check_sticker(face, row, col);
+        self.faces[face as usize][row * self.size + col] = color;
+    }
+
+    /// Every sticker as `(face, row, col, color)`, with coordinates as in [`RubiksCube::get`].
+    ///
+    /// This is the crate's canonical sticker order, which everything that lists or writes out
+    /// stickers follows: faces by index from [`Face::Up`] (0) to [`Face::Down`] (5), each face
+    /// row by row from row 0, and each row from column 0. On a 3x3x3 it starts at `(Up, 0, 0)`
+    /// and ends at `(Down, 2, 2)`.
+    pub fn stickers(&self) -> impl Iterator<Item = (Face, usize, usize, Color)> + '_ {
+        self.into_iter().flat_map(move |(face, stickers)| {
+            stickers
+                .iter()
+                .enumerate()
+                .map(move |(i, &color)| (face, i / self.size, i % self.size, color))
+        })
+    }
+
+    /// The sticker letters of each face, as [`Color::to_char`] writes them, for frontends that
+    /// want plain data without the `serde` feature. The array is indexed by face from
+    /// [`Face::Up`] (0) through Left, Front, Right and Back to [`Face::Down`] (5), and each face
+    /// is a list of rows from row 0, each listing columns from column 0: the canonical
+    /// [`RubiksCube::stickers`] order. Faces are laid out as in the printed net, so Left, Front,
+    /// Right and Back share their top row with Up.
+    pub fn snapshot(&self) -> [Vec<Vec<char>>; 6] {
+        self.faces.each_ref().map(|stickers| {
+            stickers
+                .chunks(self.size)
+                .map(|row| row.iter().map(|color| color.to_char()).collect())
+                .collect()
+        })
+    }
+
+    /// Every sticker where this cube and `other` disagree, in the canonical
+    /// [`RubiksCube::stickers`] order. Cubes of different sizes can't be compared.
+    pub fn diff(&self, other: &RubiksCube) -> Result<Vec<StickerDiff>, String> {
+        self.check_same_size(other)?;
+        Ok(self
+            .stickers()
+            .zip(other.stickers())
+            .filter(|((.., ours), (.., theirs))| ours != theirs)
+            .map(|((face, row, col, ours), (.., theirs))| (face, row, col, ours, theirs))
+            .collect())
+    }
+
+    /// How many stickers this cube and `other` disagree on, the length of
+    /// [`RubiksCube::diff`] without listing them. Measured against a solved cube, it's a rough
+    /// count of how scrambled this one is. Cubes of different sizes can't be compared.
+    pub fn hamming_distance(&self, other: &RubiksCube) -> Result<usize, String> {
+        self.check_same_size(other)?;
+        Ok(self
+            .faces
+            .iter()
+            .flatten()
+            .zip(other.faces.iter().flatten())
+            .filter(|(ours, theirs)| ours != theirs)
+            .count())
+    }
+
+    fn check_same_size(&self, other: &RubiksCube) -> Result<(), String> {
+        if self.size == other.size {
+            Ok(())
+        } else {
+            Err(format!(
+                "can't compare a {0}x{0}x{0} cube with a {1}x{1}x{1}",
+                self.size, other.size
+            ))
+        }
+    }
+
+    fn check_sticker(&self, face: Face, row: usize, col: usize) {
+        assert!(
+            row < self.size && col < self.size,
+            "sticker ({row}, {col}) is off the {face:?} face of a {0}x{0}x{0} cube",
+            self.size
+        );
+    }
+
+    /// Writes the cube in the facelet format used by Kociemba-style solvers: the faces in
+    /// U, R, F, D, L, B order, each read row by row as laid out in the net, with every sticker
+    /// named by the face whose center has its color. Only 3x3x3 cubes have this format.
+    pub fn to_facelet_string(&self) -> String {
+        assert_eq!(self.size, 3, "facelet strings only describe 3x3x3 cubes");
+
+        let center = |face: Face| self.get(face, 1, 1);
+        FACELET_ORDER
+            .iter()
+            .flat_map(|&face| &self[face])
+            .map(|&color| {
+                let face = FACELET_ORDER
+                    .into_iter()
+                    .find(|&face| center(face) == color)
+                    .unwrap_or_else(|| home_face(color));
+                face_letter(face)
+            })
+            .collect()
+    }
+
+    /// Reads a cube written by [`RubiksCube::to_facelet_string`], coloring each face letter
+    /// with that face's color on a solved cube.
+    pub fn from_facelet_string(s: &str) -> Result<RubiksCube, String> {
+        let letters: Vec<char> = s.chars().collect();
+        if letters.len() != 54 {
+            return Err(format!(
+                "facelet string must be 54 characters long, got {}",
+                letters.len()
+            ));
+        }
+
+        let mut rc = RubiksCube::new(3);
+        for (&face, stickers) in FACELET_ORDER.iter().zip(letters.chunks(9)) {
+            for (i, &letter) in stickers.iter().enumerate() {
+                let sticker_face = face_from_letter(letter).ok_or_else(|| {
+                    format!("unknown facelet {letter:?}, expected one of U, R, F, D, L, B")
+                })?;
+                rc.set(face, i / 3, i % 3, solved_color(sticker_face));
+            }
+        }
+        Ok(rc)
+    }
+}
+
+const FACELET_ORDER: [Face; 6] = [
+    Face::Up,
+    Face::Right,
+    Face::Front,
+    Face::Down,
+    Face::Left,
+    Face::Back,
+];
+
+fn face_letter(face: Face) -> char {
+    match face {
+        Face::Up => 'U',
+        Face::Left => 'L',
+        Face::Front => 'F',
+        Face::Right => 'R',
+        Face::Back => 'B',
+        Face::Down => 'D',
+    }
+}
+
+fn face_from_letter(letter: char) -> Option<Face> {
+    match letter {
+        'U' => Some(Face::Up),
+        'L' => Some(Face::Left),
+        'F' => Some(Face::Front),
+        'R' => Some(Face::Right),
+        'B' => Some(Face::Back),
+        'D' => Some(Face::Down),
+        _ => None,
+    }
+}
+
+/// Every color, in the order of their discriminants.
+pub(crate) const COLORS: [Color; 6] = [
+    Color::White,
+    Color::Yellow,
+    Color::Red,
+    Color::Orange,
+    Color::Blue,
+    Color::Green,
+];
+
+fn solved_color(face: Face) -> Color {
+    match face {
+        Face::Up => Color::Yellow,
+        Face::Left => Color::Orange,
+        Face::Front => Color::Blue,
+        Face::Right => Color::Red,
+        Face::Back => Color::Green,
+        Face::Down => Color::White,
+    }
+}
+
+fn home_face(color: Color) -> Face {
+    match color {
+        Color::Yellow => Face::Up,
+        Color::Orange => Face::Left,
+        Color::Blue => Face::Front,
+        Color::Red => Face::Right,
+        Color::Green => Face::Back,
+        Color::White => Face::Down,
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct SerializedCube {
+    size: usize,
+    faces: [Vec<Color>; 6],
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<SerializedCube> for RubiksCube {
+    type Error = String;
+
+    fn try_from(cube: SerializedCube) -> Result<Self, Self::Error> {
+        let SerializedCube { size, faces } = cube;
+        for (i, face) in faces.iter().enumerate() {
+            if face.len() != size * size {
+                return Err(format!(
+                    "face {i} has {} stickers but a {size}x{size}x{size} cube needs {}",
+                    face.len(),
+                    size * size
+                ));
+            }
+        }
+        Ok(RubiksCube {
+            size,
+            faces,
+            history: Some(Vec::new()),
+            orientations: None,
+        })
+    }
+}
+
+fn all_same(mut colors: impl Iterator<Item = Color>) -> bool {
+    match colors.next() {
+        Some(first) => colors.all(|color| color == first),
+        None => true,
+    }
+}
+
+/// A sticker two cubes disagree on, as `(face, row, col, ours, theirs)`. See
+/// [`RubiksCube::diff`].
+pub type StickerDiff = (Face, usize, usize, Color, Color);
+
+/// Iterates over the faces of a cube in [`Face`] order, each as its stickers in row-major order.
+pub struct Faces<'a> {
+    rc: &'a RubiksCube,
+    next: usize,
+}
+
+impl<'a> Iterator for Faces<'a> {
+    type Item = (Face, &'a [Color]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let face = num_traits::FromPrimitive::from_usize(self.next)?;
+        self.next += 1;
+        Some((face, &self.rc[face]))
+    }
+}
+
+/// A face's stickers, row by row as in [`RubiksCube::get`]: `rc[Face::Front]`.
+impl Index<Face> for RubiksCube {
+    type Output = [Color];
+
+    fn index(&self, face: Face) -> &[Color] {
+        &self.faces[face as usize]
+    }
+}
+
+impl IndexMut<Face> for RubiksCube {
+    fn index_mut(&mut self, face: Face) -> &mut [Color] {
+        &mut self.faces[face as usize]
+    }
+}
+
+impl<'a> IntoIterator for &'a RubiksCube {
+    type Item = (Face, &'a [Color]);
+    type IntoIter = Faces<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Faces { rc: self, next: 0 }
+    }
+}
+
+impl Display for RubiksCube {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        self.write_net(fmt, &ColorScheme::default())
+    }
+}
+
+impl RubiksCube {
+    fn write_net(&self, fmt: &mut Formatter<'_>, scheme: &ColorScheme) -> core::fmt::Result {
+        let output_single_row =
+            |fmt: &mut Formatter<'_>, face: Face, row: usize| -> core::fmt::Result {
+                for col in 0..self.size {
+                    let c = self.get(face, row, col);
+                    match self.orientation(face, row, col) {
+                        Some(orientation) if supercube::is_center(self.size, row, col) => {
+                            scheme::write_marked_sticker(fmt, c, scheme.rgb(c), orientation)?
+                        }
+                        _ => scheme::write_sticker(fmt, c, scheme.rgb(c))?,
+                    }
+                }
+                Ok(())
+            };
+
+        let sticker_width = if scheme::colors_enabled() { 2 } else { 3 };
+        let leading_spaces = self.size * sticker_width;
+
+        for row in 0..self.size {
+            write!(fmt, "{:leading_spaces$}", "")?;
+            output_single_row(fmt, Face::Up, row)?;
+            writeln!(fmt)?;
+        }
+
+        for row in 0..self.size {
+            for face in [Face::Left, Face::Front, Face::Right, Face::Back] {
+                output_single_row(fmt, face, row)?;
+            }
+            writeln!(fmt)?;
+        }
+
+        for row in 0..self.size {
+            write!(fmt, "{:leading_spaces$}", "")?;
+            output_single_row(fmt, Face::Down, row)?;
+            writeln!(fmt)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Color {
+    #[cfg_attr(feature = "serde", serde(rename = "W"))]
+    White,
+    #[cfg_attr(feature = "serde", serde(rename = "Y"))]
+    Yellow,
+    #[cfg_attr(feature = "serde", serde(rename = "R"))]
+    Red,
+    #[cfg_attr(feature = "serde", serde(rename = "O"))]
+    Orange,
+    #[cfg_attr(feature = "serde", serde(rename = "B"))]
+    Blue,
+    #[cfg_attr(feature = "serde", serde(rename = "G"))]
+    Green,
+}
+
+#[derive(Clone, Copy, num_derive::FromPrimitive, Debug, PartialEq, Eq, Hash)]
+pub enum Face {
+    Up,
+    Left,
+    Front,
+    Right,
+    Back,
+    Down,
+}
+
+#[derive(Clone, Copy)]
+enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Movement {
+    Clockwise,
+    CounterClockwise,
+    Half,
+}
+
+/// A single layer turn, as taken by [`rotate_face`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Move {
+    pub face: Face,
+    pub movement: Movement,
+    pub depth: usize,
+}
+
+/// An axis of the whole cube, named after the rotation around it: x turns with R, y with U and
+/// z with F.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Axis {
+    X,
+    Y,
+    Z,
+}
+
+/// A middle layer, named as in standard notation. Each slice turns the same way as the face it
+/// follows: M as L, E as D and S as F.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Slice {
+    M,
+    E,
+    S,
+}
+
+/// One token of an algorithm: a single layer turn (by depth), a wide turn of the outer layers
+/// of a face (by layer count), or a rotation of the whole cube.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoveToken {
+    Turn(Face, Movement, usize),
+    Wide(Face, Movement, usize),
+    Rotation(Axis, Movement),
+}
+
+#[derive(Clone, Copy)]
+struct Side(Face, Corner);
+
+impl Movement {
+    /// The movement that undoes this one.
+    pub fn inverse(self) -> Movement {
+        match self {
+            Movement::Clockwise => Movement::CounterClockwise,
+            Movement::CounterClockwise => Movement::Clockwise,
+            Movement::Half => Movement::Half,
+        }
+    }
+
+    /// The single movement that does the same as this one followed by `other` on the same
+    /// layer, or `None` if together they make a full turn and cancel out.
+    pub fn compose(self, other: Movement) -> Option<Movement> {
+        Movement::from_quarter_turns(self.quarter_turns() + other.quarter_turns())
+    }
+
+    /// The movement that makes `quarter_turns` clockwise quarter turns, or `None` for a whole
+    /// number of full turns.
+    fn from_quarter_turns(quarter_turns: u8) -> Option<Movement> {
+        match quarter_turns % 4 {
+            0 => None,
+            1 => Some(Movement::Clockwise),
+            2 => Some(Movement::Half),
+            _ => Some(Movement::CounterClockwise),
+        }
+    }
+
+    fn quarter_turns(self) -> u8 {
+        match self {
+            Movement::Clockwise => 1,
+            Movement::Half => 2,
+            Movement::CounterClockwise => 3,
+        }
+    }
+}
+
+impl Color {
+    /// The color's uppercase initial, as used in facelet-style input and the ASCII net.
+    pub fn to_char(self) -> char {
+        match self {
+            Color::White => 'W',
+            Color::Yellow => 'Y',
+            Color::Red => 'R',
+            Color::Orange => 'O',
+            Color::Blue => 'B',
+            Color::Green => 'G',
+        }
+    }
+
+    /// The color stickers are drawn in. White is xterm palette color 255 rather than pure
+    /// white.
+    fn rgb(self) -> (u8, u8, u8) {
+        match self {
+            Color::White => (238, 238, 238),
+            Color::Yellow => (255, 255, 0),
+            Color::Red => (255, 0, 0),
+            Color::Orange => (255, 100, 0),
+            Color::Blue => (0, 0, 255),
+            Color::Green => (0, 140, 0),
+        }
+    }
+
+    /// Reads a color from its initial, one of `W Y R O B G` in either case.
+    pub fn from_char(c: char) -> Option<Color> {
+        match c.to_ascii_uppercase() {
+            'W' => Some(Color::White),
+            'Y' => Some(Color::Yellow),
+            'R' => Some(Color::Red),
+            'O' => Some(Color::Orange),
+            'B' => Some(Color::Blue),
+            'G' => Some(Color::Green),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Color {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        scheme::write_sticker(fmt, *self, self.rgb())
+    }
+}
+
+impl TryFrom<u8> for Color {
+    type Error = String;
+
+    /// The color whose discriminant is `value`, as given by `color as u8`.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        COLORS
+            .get(value as usize)
+            .copied()
+            .ok_or_else(|| format!("{value} isn't a color; colors go from 0 to 5"))
+    }
+}
+
+impl TryFrom<u8> for Face {
+    type Error = String;
+
+    /// The face whose discriminant is `value`, as given by `face as u8`.
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        num_traits::FromPrimitive::from_u8(value)
+            .ok_or_else(|| format!("{value} isn't a face; faces go from 0 to 5"))
+    }
+}
+
+/// Turns terminal colors on or off for every cube and sticker printed from now on. With colors
+/// off, stickers print as bracketed letters such as `[W]` instead.
+#[cfg(feature = "std")]
+pub fn set_color_enabled(enabled: bool) {
+    if enabled {
+        yansi::Paint::enable();
+    } else {
+        yansi::Paint::disable();
+    }
+}
+
+impl Face {
+    /// Every face, in the order of their discriminants.
+    pub const ALL: [Face; 6] = [
+        Face::Up,
+        Face::Left,
+        Face::Front,
+        Face::Right,
+        Face::Back,
+        Face::Down,
+    ];
+
+    /// The face on the other side of the cube.
+    pub fn opposite(self) -> Face {
+        use Face::*;
+        match self {
+            Up => Down,
+            Left => Right,
+            Front => Back,
+            Right => Left,
+            Back => Front,
+            Down => Up,
+        }
+    }
+
+    /// The four faces that border this one, in the order a clockwise turn of this face carries
+    /// stickers between them: each face's edge goes to the next one's.
+    pub fn adjacent(self) -> [Face; 4] {
+        get_sides(self).map(|Side(face, _)| face)
+    }
+
+    /// The axis this face sits across: x for left and right, y for up and down, z for front
+    /// and back.
+    pub fn axis(self) -> Axis {
+        match self {
+            Face::Left | Face::Right => Axis::X,
+            Face::Up | Face::Down => Axis::Y,
+            Face::Front | Face::Back => Axis::Z,
+        }
+    }
+}
+
+impl Axis {
+    /// The faces at either end of the axis, the one rotations around it follow first: (R, L)
+    /// for x, (U, D) for y and (F, B) for z.
+    pub fn faces(self) -> (Face, Face) {
+        match self {
+            Axis::X => (Face::Right, Face::Left),
+            Axis::Y => (Face::Up, Face::Down),
+            Axis::Z => (Face::Front, Face::Back),
+        }
+    }
+}
+
+/// The position of the piece a sticker is on, as `(x, y, z)` with x running from left to right,
+/// y from down to up and z from back to front.
+fn sticker_position(size: usize, face: Face, row: usize, col: usize) -> (usize, usize, usize) {
+    let s = size - 1;
+    match face {
+        Face::Up => (col, s, row),
+        Face::Down => (col, 0, s - row),
+        Face::Front => (col, s - row, s),
+        Face::Back => (s - col, s - row, 0),
+        Face::Left => (0, s - row, col),
+        Face::Right => (s, s - row, s - col),
+    }
+}
+
+fn get_sides(face: Face) -> [Side; 4] {
+    use Corner::*;
+    use Face::*;
+    match face {
+        Up => [
+            Side(Back, TopRight),
+            Side(Right, TopRight),
+            Side(Front, TopRight),
+            Side(Left, TopRight),
+        ],
+        Left => [
+            Side(Up, TopLeft),
+            Side(Front, TopLeft),
+            Side(Down, TopLeft),
+            Side(Back, BottomRight),
+        ],
+        Front => [
+            Side(Up, BottomLeft),
+            Side(Right, TopLeft),
+            Side(Down, TopRight),
+            Side(Left, BottomRight),
+        ],
+        Right => [
+            Side(Up, BottomRight),
+            Side(Back, TopLeft),
+            Side(Down, BottomRight),
+            Side(Front, BottomRight),
+        ],
+        Back => [
+            Side(Up, TopRight),
+            Side(Left, TopLeft),
+            Side(Down, BottomLeft),
+            Side(Right, BottomRight),
+        ],
+        Down => [
+            Side(Front, BottomLeft),
+            Side(Right, BottomLeft),
+            Side(Back, BottomLeft),
+            Side(Left, BottomLeft),
+        ],
+    }
+}
+
+fn position_based_off_corner_and_move_count(
+    corner: Corner,
+    move_count: usize,
+    size: usize,
+    depth: usize,
+) -> (usize, usize) {
+    match corner {
+        Corner::TopLeft => (move_count, depth),
+        Corner::TopRight => (depth, size - 1 - move_count),
+        Corner::BottomRight => (size - 1 - move_count, size - 1 - depth),
+        Corner::BottomLeft => (size - 1 - depth, move_count),
+    }
+}
+
+/// Sticker access shared by the cube types, so they can all be turned by the same code. What's
+/// stored per sticker is usually its color, but anything that moves with the stickers will do.
+trait Stickers {
+    type Sticker: Copy;
+
+    fn size(&self) -> usize;
+    fn get(&self, face: Face, row: usize, col: usize) -> Self::Sticker;
+    fn set(&mut self, face: Face, row: usize, col: usize, sticker: Self::Sticker);
+}
+
+impl Stickers for RubiksCube {
+    type Sticker = Color;
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn get(&self, face: Face, row: usize, col: usize) -> Color {
+        RubiksCube::get(self, face, row, col)
+    }
+
+    fn set(&mut self, face: Face, row: usize, col: usize, color: Color) {
+        RubiksCube::set(self, face, row, col, color)
+    }
+}
+
+macro_rules! cycle {
+    ($rc:expr, $a:expr, $b:expr) => {{
+        let (a, b) = ($a, $b);
+        let temp = $rc.get(a.0, a.1, a.2);
+        $rc.set(a.0, a.1, a.2, $rc.get(b.0, b.1, b.2));
+        $rc.set(b.0, b.1, b.2, temp);
+    }};
+    ($rc:expr, $a:expr, $b:expr, $c:expr, $d:expr) => {{
+        let (a, b, c, d) = ($a, $b, $c, $d);
+        let temp = $rc.get(a.0, a.1, a.2);
+        $rc.set(a.0, a.1, a.2, $rc.get(b.0, b.1, b.2));
+        $rc.set(b.0, b.1, b.2, $rc.get(c.0, c.1, c.2));
+        $rc.set(c.0, c.1, c.2, $rc.get(d.0, d.1, d.2));
+        $rc.set(d.0, d.1, d.2, temp);
+    }};
+}
+
+/// Converts a layer index between the two ends of `face`'s axis.
+///
+/// With `from_opposite` set, `depth` is taken as counted from the face opposite `face` and the
+/// same layer's depth counted from `face` is returned; otherwise `depth` is returned unchanged.
+/// The mapping is its own inverse, so it also converts the other way.
+pub fn normalize_depth(face: Face, depth: usize, from_opposite: bool, size: usize) -> usize {
+    assert!(
+        depth < size,
+        "depth {depth} is out of range for {face:?} on a {size}x{size}x{size} cube"
+    );
+    if from_opposite {
+        size - 1 - depth
+    } else {
+        depth
+    }
+}
+
+fn rotate_face_grid(rc: &mut impl Stickers, face: Face, movement: Movement) {
+    let f = face;
+    let size = rc.size();
+    let s = size - 1;
+    match movement {
+        Movement::Clockwise => {
+            for o in 0..(size / 2) {
+                for i in o..(s - o) {
+                    cycle!(
+                        rc,
+                        (f, o, i),
+                        (f, s - i, o),
+                        (f, s - o, s - i),
+                        (f, i, s - o)
+                    );
+                }
+            }
+        }
+        Movement::CounterClockwise => {
+            for o in 0..(size / 2) {
+                for i in o..(s - o) {
+                    cycle!(
+                        rc,
+                        (f, o, i),
+                        (f, i, s - o),
+                        (f, s - o, s - i),
+                        (f, s - i, o)
+                    );
+                }
+            }
+        }
+        Movement::Half => {
+            for o in 0..(size / 2) {
+                for i in o..(s - o) {
+                    cycle!(rc, (f, o, i), (f, s - o, s - i));
+                    cycle!(rc, (f, s - i, o), (f, i, s - o));
+                }
+            }
+        }
+    };
+}
+
+/// Why a turn couldn't be made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RotateError {
+    /// The cube has no layers to turn.
+    EmptyCube,
+    /// `depth` is `size` or more, so it's past the far side of the cube.
+    DepthOutOfRange {
+        face: Face,
+        depth: usize,
+        size: usize,
+    },
+}
+
+impl Display for RotateError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            RotateError::EmptyCube => write!(fmt, "can't turn a cube with no layers"),
+            RotateError::DepthOutOfRange { face, depth, size } => write!(
+                fmt,
+                "depth {depth} is out of range for {face:?} on a {size}x{size}x{size} cube"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for RotateError {}
+
+fn check_depth(rc: &RubiksCube, face: Face, depth: usize) -> Result<(), RotateError> {
+    if rc.size == 0 {
+        Err(RotateError::EmptyCube)
+    } else if depth >= rc.size {
+        Err(RotateError::DepthOutOfRange {
+            face,
+            depth,
+            size: rc.size,
+        })
+    } else {
+        Ok(())
+    }
+}
+
+/// Like [`rotate_face`], but returns an error instead of panicking when `depth` isn't a layer
+/// of the cube. The valid depths are `0..size`: 0 is `face`'s own layer and `size - 1` is the
+/// opposite face's.
+pub fn try_rotate_face(
+    rc: &mut RubiksCube,
+    face: Face,
+    movement: Movement,
+    depth: usize,
+) -> Result<(), RotateError> {
+    check_depth(rc, face, depth)?;
+    rotate_face(rc, face, movement, depth);
+    Ok(())
+}
+
+/// Turns one layer of the cube as seen from `face`.
+///
+/// `depth` counts layers inward from `face`: 0 is the outer layer of `face` itself (the only one
+/// that also turns the face's stickers) and `size - 1` is the outer layer of the opposite face.
+/// `movement` is always as seen looking at `face`, so turning the far layer clockwise from
+/// `face` is the same as turning the opposite face counter-clockwise at depth 0.
+///
+/// Panics if `depth` isn't a layer of the cube; see [`try_rotate_face`] for a checked version.
+pub fn rotate_face(rc: &mut RubiksCube, face: Face, movement: Movement, depth: usize) {
+    if let Err(e) = check_depth(rc, face, depth) {
+        panic!("{e}");
+    }
+    if let Some(orientations) = &mut rc.orientations {
+        supercube::turn_orientations(orientations, rc.size, face, movement, depth);
+    }
+    #[cfg(feature = "parallel")]
+    if rc.size >= PARALLEL_THRESHOLD {
+        parallel::turn_layer(rc, face, movement, depth);
+        return;
+    }
+    turn_layer(rc, face, movement, depth);
+}
+
+/// Turns one layer of the cube `quarter_turns` clockwise quarter turns as seen from `face`, or
+/// counter-clockwise if negative, in a single pass: any count is first reduced to one
+/// [`Movement`], and whole turns do nothing. Otherwise the same as [`rotate_face`].
+pub fn rotate_face_n(rc: &mut RubiksCube, face: Face, depth: usize, quarter_turns: i32) {
+    if let Err(e) = check_depth(rc, face, depth) {
+        panic!("{e}");
+    }
+    if let Some(movement) = Movement::from_quarter_turns(quarter_turns.rem_euclid(4) as u8) {
+        rotate_face(rc, face, movement, depth);
+    }
+}
+
+/// The body of [`rotate_face`], for any cube type. `depth` must already be checked.
+fn turn_layer(rc: &mut impl Stickers, face: Face, movement: Movement, depth: usize) {
+    let size = rc.size();
+    if depth == 0 {
+        rotate_face_grid(rc, face, movement);
+    }
+    if depth == size - 1 {
+        rotate_face_grid(rc, face.opposite(), movement.inverse());
+    }
+
+    let sides = get_sides(face);
+    let position = |side: usize, i: usize| {
+        let (row, col) = position_based_off_corner_and_move_count(sides[side].1, i, size, depth);
+        (sides[side].0, row, col)
+    };
+    match movement {
+        Movement::Clockwise => {
+            for i in 0..size {
+                cycle!(
+                    rc,
+                    position(0, i),
+                    position(3, i),
+                    position(2, i),
+                    position(1, i)
+                );
+            }
+        }
+        Movement::CounterClockwise => {
+            for i in 0..size {
+                cycle!(
+                    rc,
+                    position(0, i),
+                    position(1, i),
+                    position(2, i),
+                    position(3, i)
+                );
+            }
+        }
+        Movement::Half => {
+            for i in 0..size {
+                cycle!(rc, position(0, i), position(2, i));
+                cycle!(rc, position(1, i), position(3, i));
+            }
+        }
+    }
+}
+
+/// Turns the middle layer named by `slice`, in the direction of the face it follows.
+///
+/// On odd cubes that is the single center layer. Even cubes have no single middle layer, so both
+/// of the layers either side of the center turn together.
+pub fn rotate_slice(rc: &mut RubiksCube, slice: Slice, movement: Movement) {
+    let face = match slice {
+        Slice::M => Face::Left,
+        Slice::E => Face::Down,
+        Slice::S => Face::Front,
+    };
+    if rc.size.is_multiple_of(2) {
+        rotate_face(rc, face, movement, rc.size / 2 - 1);
+    }
+    rotate_face(rc, face, movement, rc.size / 2);
+}
+
+/// Returns a copy of `rc` with one layer turned, leaving `rc` as it was.
+pub fn moved(rc: &RubiksCube, face: Face, movement: Movement, depth: usize) -> RubiksCube {
+    let mut rc = rc.clone();
+    rotate_face(&mut rc, face, movement, depth);
+    rc
+}
+
+/// The single layer turn that takes `before` to `after`, or `None` if no one turn does: when the
+/// cubes are the same, are different sizes, or are further apart.
+///
+/// Turns are tried outer layers first, so a layer that can be named from either side comes back
+/// named from the nearer face, like `L` rather than the far layer of `R`. The first match wins.
+pub fn infer_move(before: &RubiksCube, after: &RubiksCube) -> Option<Move> {
+    if before.size != after.size || before == after {
+        return None;
+    }
+    let mut candidate = before.clone();
+    for depth in 0..before.size {
+        for face in Face::ALL {
+            for movement in [
+                Movement::Clockwise,
+                Movement::CounterClockwise,
+                Movement::Half,
+            ] {
+                rotate_face(&mut candidate, face, movement, depth);
+                let found = candidate == *after;
+                rotate_face(&mut candidate, face, movement.inverse(), depth);
+                if found {
+                    return Some(Move {
+                        face,
+                        movement,
+                        depth,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Turns the layers of `face` at every depth in `depths` together, as one block. Only a range
+/// starting at 0 turns `face`'s own stickers, and only one reaching `size` turns the opposite
+/// face's. An empty range does nothing.
+///
+/// Panics, before turning anything, if the range goes past the far side of the cube.
+pub fn rotate_layers(rc: &mut RubiksCube, face: Face, movement: Movement, depths: Range<usize>) {
+    if let Some(last) = depths.clone().next_back() {
+        if let Err(e) = check_depth(rc, face, last) {
+            panic!("{e}");
+        }
+    }
+    for depth in depths {
+        rotate_face(rc, face, movement, depth);
+    }
+}
+
+/// Turns the outer `layers` layers of `face` together, as in wide moves like `Rw` or `3Rw`.
+pub fn rotate_wide(
+    rc: &mut RubiksCube,
+    face: Face,
+    movement: Movement,
+    layers: usize,
+) -> Result<(), String> {
+    if layers == 0 || layers > rc.size {
+        return Err(format!(
+            "can't turn {layers} layers of {face:?} on a {0}x{0}x{0} cube",
+            rc.size
+        ));
+    }
+    rotate_layers(rc, face, movement, 0..layers);
+    Ok(())
+}
+
+/// Turns the single interior layer `index` layers in from the face `axis` turns with (R for x,
+/// U for y, F for z), in that face's direction. Outer layers are rejected since turning them
+/// would also turn a face's stickers.
+pub fn rotate_inner_slice(rc: &mut RubiksCube, axis: Axis, index: usize, movement: Movement) {
+    assert!(
+        index > 0 && index + 1 < rc.size,
+        "slice {index} is not an inner slice of a {0}x{0}x{0} cube",
+        rc.size
+    );
+    rotate_face(rc, axis.faces().0, movement, index);
+}
+
+/// Rewrites `moves` without whole-cube rotations.
+///
+/// Turns after a rotation name the faces where they are now, so each one is translated back to
+/// the face it was on before any rotation. Applying the result leaves the same stickers in the
+/// same places as `moves`, apart from the cube's overall orientation.
+pub fn eliminate_rotations(moves: &[MoveToken]) -> Vec<(Face, Movement, usize)> {
+    use Face::*;
+
+    // orientation[position] is the face that was originally at that position.
+    let mut orientation = Face::ALL;
+    let mut absolute = Vec::with_capacity(moves.len());
+
+    for &token in moves {
+        match token {
+            MoveToken::Turn(face, movement, depth) => {
+                absolute.push((orientation[face as usize], movement, depth));
+            }
+            MoveToken::Wide(face, movement, layers) => {
+                absolute
+                    .extend((0..layers).map(|depth| (orientation[face as usize], movement, depth)));
+            }
+            MoveToken::Rotation(axis, movement) => {
+                // Each face moves to the next position in its cycle on a clockwise rotation.
+                let cycle = match axis {
+                    Axis::X => [Front, Up, Back, Down],
+                    Axis::Y => [Front, Left, Back, Right],
+                    Axis::Z => [Up, Right, Down, Left],
+                };
+                for _ in 0..movement.quarter_turns() {
+                    let last = orientation[cycle[3] as usize];
+                    for i in (1..4).rev() {
+                        orientation[cycle[i] as usize] = orientation[cycle[i - 1] as usize];
+                    }
+                    orientation[cycle[0] as usize] = last;
+                }
+            }
+        }
+    }
+
+    absolute
+}
+
+/// Lists every turn that leaves the stickers of `face` where they are.
+///
+/// Every layer on the other two axes crosses `face`, so only layers parallel to it qualify. They
+/// are all expressed from the opposite face, from its outer layer inward up to (but not
+/// including) `face`'s own outer layer.
+pub fn moves_preserving(face: Face, size: usize) -> Vec<(Face, Movement, usize)> {
+    let opposite = face.opposite();
+    let mut moves = Vec::with_capacity(3 * size.saturating_sub(1));
+    for depth in 0..size.saturating_sub(1) {
+        for movement in [
+            Movement::Clockwise,
+            Movement::CounterClockwise,
+            Movement::Half,
+        ] {
+            moves.push((opposite, movement, depth));
+        }
+    }
+    moves
+}
+
+fn percent_encode(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(b as char)
+            }
+            _ => encoded.push_str(&format!("%{b:02X}")),
+        }
+    }
+    encoded
+}
+
+/// Builds an alg.cubing.net link that plays `solution` on a cube scrambled with `scramble`.
+pub fn to_alg_url(scramble: &[(Face, Movement)], solution: &[(Face, Movement)]) -> String {
+    let alg = |moves: &[(Face, Movement)]| {
+        let notation: Vec<String> = moves
+            .iter()
+            .map(|&(face, movement)| format!("{face}{movement}"))
+            .collect();
+        percent_encode(&notation.join(" "))
+    };
+    format!(
+        "https://alg.cubing.net/?setup={}&alg={}",
+        alg(scramble),
+        alg(solution)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn random_3x3x3_clockwise_scramble() {
+        let mut rc = crate::RubiksCube::new(3);
+
+        use crate::Color::*;
+        use crate::Face::*;
+
+        let moves = [
+            Down, Left, Up, Front, Left, Up, Down, Right, Down, Left, Front, Back, Left, Right,
+            Back, Up, Down, Front, Up, Down, Back, Left, Front, Left, Right, Left, Down, Left,
+            Front, Back,
+        ];
+
+        for m in moves {
+            crate::rotate_face(&mut rc, m, crate::Movement::Clockwise, 0);
+        }
+
+        let expected = crate::RubiksCube {
+            size: 3,
+            faces: [
+                [
+                    vec![Green, White, Red],
+                    vec![Yellow, Yellow, Red],
+                    vec![Blue, Red, Red],
+                ]
+                .concat(),
+                [
+                    vec![White, Blue, Orange],
+                    vec![Blue, Orange, Orange],
+                    vec![Green, Green, Orange],
+                ]
+                .concat(),
+                [
+                    vec![White, White, Yellow],
+                    vec![Yellow, Blue, Orange],
+                    vec![White, White, Orange],
+                ]
+                .concat(),
+                [
+                    vec![Blue, Yellow, Blue],
+                    vec![Blue, Red, Red],
+                    vec![Green, Yellow, Yellow],
+                ]
+                .concat(),
+                [
+                    vec![White, Green, Red],
+                    vec![Blue, Green, White],
+                    vec![Blue, Orange, Yellow],
+                ]
+                .concat(),
+                [
+                    vec![Green, Orange, Yellow],
+                    vec![Red, White, Green],
+                    vec![Red, Green, Orange],
+                ]
+                .concat(),
+            ],
+            history: None,
+            orientations: None,
+        };
+
+        assert_eq!(rc, expected);
+    }
+
+    #[test]
+    fn random_3x3x3_mixed_scramble() {
+        let mut rc = crate::RubiksCube::new(3);
+
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let moves = [
+            (Up, Half),
+            (Right, Half),
+            (Down, Half),
+            (Front, CounterClockwise),
+            (Down, Clockwise),
+            (Up, Half),
+            (Back, CounterClockwise),
+            (Left, Clockwise),
+            (Front, Clockwise),
+            (Up, Half),
+            (Front, Half),
+            (Down, Clockwise),
+            (Right, CounterClockwise),
+            (Down, Clockwise),
+            (Left, CounterClockwise),
+            (Back, Half),
+            (Front, Half),
+            (Back, CounterClockwise),
+            (Right, Clockwise),
+            (Down, CounterClockwise),
+            (Left, CounterClockwise),
+            (Front, Clockwise),
+            (Left, Clockwise),
+            (Down, Half),
+            (Up, CounterClockwise),
+            (Right, Half),
+            (Back, Clockwise),
+            (Front, CounterClockwise),
+            (Left, CounterClockwise),
+            (Up, Half),
+        ];
+
+        for (face, movement) in moves {
+            crate::rotate_face(&mut rc, face, movement, 0);
+        }
+
+        let expected = crate::RubiksCube {
+            size: 3,
+            faces: [
+                [
+                    vec![White, Red, Orange],
+                    vec![Orange, Yellow, Yellow],
+                    vec![Yellow, Red, Red],
+                ]
+                .concat(),
+                [
+                    vec![Orange, Yellow, Orange],
+                    vec![Orange, Orange, Yellow],
+                    vec![Yellow, Orange, Orange],
+                ]
+                .concat(),
+                [
+                    vec![Blue, Blue, Blue],
+                    vec![Red, Blue, Blue],
+                    vec![Green, Orange, Blue],
+                ]
+                .concat(),
+                [
+                    vec![White, Blue, Green],
+                    vec![White, Red, Yellow],
+                    vec![Yellow, White, Red],
+                ]
+                .concat(),
+                [
+                    vec![White, Green, Blue],
+                    vec![Green, Green, Green],
+                    vec![Green, White, Red],
+                ]
+                .concat(),
+                [
+                    vec![Yellow, White, Red],
+                    vec![Blue, White, Green],
+                    vec![Green, Red, White],
+                ]
+                .concat(),
+            ],
+            history: None,
+            orientations: None,
+        };
+
+        assert_eq!(rc, expected);
+    }
+
+    #[test]
+    fn random_5x5x5_mixed_scramble() {
+        let mut rc = crate::RubiksCube::new(5);
+
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let moves = [
+            (Front, Half, 1),
+            (Down, Half, 1),
+            (Right, Clockwise, 1),
+            (Up, CounterClockwise, 1),
+            (Left, CounterClockwise, 0),
+            (Down, Clockwise, 0),
+            (Front, Clockwise, 0),
+            (Right, CounterClockwise, 1),
+            (Down, Clockwise, 0),
+            (Back, CounterClockwise, 2),
+            (Right, Clockwise, 1),
+            (Up, Half, 2),
+            (Front, CounterClockwise, 0),
+            (Down, Half, 0),
+            (Up, CounterClockwise, 1),
+            (Down, Clockwise, 2),
+            (Left, Clockwise, 0),
+            (Up, Half, 0),
+            (Front, Half, 2),
+            (Right, Clockwise, 1),
+            (Front, Clockwise, 1),
+            (Left, CounterClockwise, 0),
+            (Up, CounterClockwise, 1),
+            (Front, Half, 2),
+            (Left, CounterClockwise, 1),
+            (Back, Clockwise, 1),
+            (Up, Clockwise, 0),
+            (Left, Half, 1),
+            (Right, Half, 1),
+            (Back, CounterClockwise, 0),
+        ];
+
+        for (face, movement, layer) in moves {
+            crate::rotate_face(&mut rc, face, movement, layer);
+        }
+
+        let expected = crate::RubiksCube {
+            size: 5,
+            faces: [
+                [
+                    vec![White, Orange, Red, Blue, White],
+                    vec![Orange, Blue, Orange, White, Yellow],
+                    vec![Yellow, Red, Orange, Orange, Orange],
+                    vec![White, Yellow, Orange, White, Green],
+                    vec![Green, White, Red, Blue, Yellow],
+                ]
+                .concat(),
+                [
+                    vec![Green, Yellow, Green, Green, Red],
+                    vec![Red, Red, Green, Yellow, White],
+                    vec![Red, White, Blue, Green, Orange],
+                    vec![Red, Blue, Green, Orange, Orange],
+                    vec![Green, Orange, Blue, Red, Blue],
+                ]
+                .concat(),
+                [
+                    vec![White, Blue, Yellow, Yellow, Orange],
+                    vec![Red, Green, Orange, White, Orange],
+                    vec![Green, Green, Yellow, Yellow, Orange],
+                    vec![White, Red, Red, Blue, Yellow],
+                    vec![Yellow, Red, Blue, Blue, Blue],
+                ]
+                .concat(),
+                [
+                    vec![Blue, Yellow, Yellow, Blue, Blue],
+                    vec![Blue, Green, White, Blue, Red],
+                    vec![White, Yellow, Green, White, Orange],
+                    vec![Green, White, Yellow, Orange, Green],
+                    vec![Red, Yellow, White, Orange, Green],
+                ]
+                .concat(),
+                [
+                    vec![Orange, White, Blue, White, Orange],
+                    vec![Blue, Yellow, Red, Orange, Yellow],
+                    vec![Blue, Blue, White, Blue, Green],
+                    vec![Red, Green, Blue, Yellow, White],
+                    vec![Red, White, White, Blue, Orange],
+                ]
+                .concat(),
+                [
+                    vec![Red, Green, White, Red, White],
+                    vec![Yellow, Green, Blue, Red, Orange],
+                    vec![Yellow, Red, Red, White, Red],
+                    vec![Green, Orange, Yellow, Red, Green],
+                    vec![Yellow, Orange, Green, Green, Yellow],
+                ]
+                .concat(),
+            ],
+            history: None,
+            orientations: None,
+        };
+
+        assert_eq!(rc, expected);
+    }
+
+    #[test]
+    fn normalize_depth_converts_between_opposite_faces() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        assert_eq!(crate::normalize_depth(Left, 1, true, 5), 3);
+        assert_eq!(crate::normalize_depth(Left, 3, true, 5), 1);
+        assert_eq!(crate::normalize_depth(Left, 1, false, 5), 1);
+        assert_eq!(crate::normalize_depth(Up, 2, true, 5), 2);
+
+        let mut from_right = crate::RubiksCube::new(5);
+        crate::rotate_face(&mut from_right, Right, Clockwise, 1);
+
+        let mut from_left = crate::RubiksCube::new(5);
+        let depth = crate::normalize_depth(Left, 1, true, 5);
+        crate::rotate_face(&mut from_left, Left, CounterClockwise, depth);
+
+        assert_eq!(from_right, from_left);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rotate_face_rejects_out_of_range_depth() {
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, crate::Face::Up, crate::Movement::Clockwise, 3);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        crate::rotate_face(&mut rc, Up, CounterClockwise, 0);
+
+        let json = serde_json::to_string(&rc).unwrap();
+        assert!(json.starts_with(r#"{"size":3,"faces":[["#));
+        assert!(json.contains(r#""B""#) && !json.contains("Blue"));
+
+        let parsed: crate::RubiksCube = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, rc);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_rejects_mismatched_size() {
+        let json = serde_json::to_string(&crate::RubiksCube::new(3)).unwrap();
+        let json = json.replacen(r#""size":3"#, r#""size":2"#, 1);
+        assert!(serde_json::from_str::<crate::RubiksCube>(&json).is_err());
+    }
+
+    #[test]
+    fn moves_preserving_leaves_face_untouched() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let moves = crate::moves_preserving(Up, 4);
+        assert_eq!(moves.len(), 9);
+        assert!(moves
+            .iter()
+            .any(|&(f, m, d)| matches!((f, m, d), (Down, Clockwise, 0))));
+        assert!(moves
+            .iter()
+            .any(|&(f, m, d)| matches!((f, m, d), (Down, Half, 2))));
+        assert!(moves.iter().all(|&(f, _, d)| matches!(f, Down) && d < 3));
+
+        let mut rc = crate::RubiksCube::new(4);
+        for (face, movement) in [(Right, Clockwise), (Front, Half), (Up, CounterClockwise)] {
+            crate::rotate_face(&mut rc, face, movement, 0);
+        }
+        for (face, movement, depth) in moves {
+            let before = rc[Up].to_vec();
+            crate::rotate_face(&mut rc, face, movement, depth);
+            assert_eq!(rc[Up], before);
+        }
+
+        let before = rc[Up].to_vec();
+        crate::rotate_face(&mut rc, Front, Clockwise, 0);
+        assert_ne!(rc[Up], before);
+    }
+
+    #[test]
+    fn facelet_string_round_trip() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let solved = crate::RubiksCube::new(3);
+        assert_eq!(
+            solved.to_facelet_string(),
+            "UUUUUUUUURRRRRRRRRFFFFFFFFFDDDDDDDDDLLLLLLLLLBBBBBBBBB"
+        );
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        assert_eq!(
+            rc.to_facelet_string(),
+            "UUFUUFUUFRRRRRRRRRFFDFFDFFDDDBDDBDDBLLLLLLLLLUBBUBBUBB"
+        );
+
+        for (face, movement) in [(Up, Clockwise), (Front, Half), (Left, CounterClockwise)] {
+            crate::rotate_face(&mut rc, face, movement, 0);
+        }
+        let facelets = rc.to_facelet_string();
+        assert_eq!(
+            crate::RubiksCube::from_facelet_string(&facelets).unwrap(),
+            rc
+        );
+    }
+
+    #[test]
+    fn facelet_string_rejects_bad_input() {
+        assert!(crate::RubiksCube::from_facelet_string("UUU").is_err());
+        let bad = "X".repeat(54);
+        let err = crate::RubiksCube::from_facelet_string(&bad).unwrap_err();
+        assert!(err.contains("'X'"));
+    }
+
+    #[test]
+    fn alg_url_encodes_moves() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let scramble = [(Right, Clockwise), (Up, CounterClockwise), (Front, Half)];
+        let solution = [(Front, Half), (Up, Clockwise), (Right, CounterClockwise)];
+        let url = crate::to_alg_url(&scramble, &solution);
+        assert_eq!(
+            url,
+            "https://alg.cubing.net/?setup=R%20U%27%20F2&alg=F2%20U%20R%27"
+        );
+
+        let decode = |s: &str| s.replace("%20", " ").replace("%27", "'");
+        let query = url.split_once('?').unwrap().1;
+        let (setup, alg) = query.split_once('&').unwrap();
+        assert_eq!(decode(setup.strip_prefix("setup=").unwrap()), "R U' F2");
+        assert_eq!(decode(alg.strip_prefix("alg=").unwrap()), "F2 U R'");
+    }
+
+    #[test]
+    fn clone_is_independent() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        crate::rotate_face(&mut rc, Up, Half, 0);
+        let original = rc.to_facelet_string();
+
+        let mut branch = rc.clone();
+        crate::rotate_face(&mut branch, Front, Clockwise, 0);
+
+        assert_eq!(rc.to_facelet_string(), original);
+        assert_ne!(branch, rc);
+    }
+
+    #[test]
+    fn eliminate_rotations_translates_faces() {
+        use crate::Axis::*;
+        use crate::Face::*;
+        use crate::MoveToken::*;
+        use crate::Movement::*;
+
+        let moves = crate::eliminate_rotations(&[Rotation(X, Clockwise), Turn(Up, Clockwise, 0)]);
+        assert_eq!(moves, [(Front, Clockwise, 0)]);
+
+        let moves = crate::eliminate_rotations(&[Rotation(X, Clockwise), Turn(Right, Half, 0)]);
+        assert_eq!(moves, [(Right, Half, 0)]);
+
+        let tokens = [
+            Rotation(Y, Clockwise),
+            Turn(Right, Clockwise, 0),
+            Rotation(Z, CounterClockwise),
+            Turn(Up, Half, 0),
+            Turn(Front, CounterClockwise, 1),
+        ];
+
+        let mut relative = crate::RubiksCube::new(3);
+        relative.rotate_cube(Y, Clockwise);
+        crate::rotate_face(&mut relative, Right, Clockwise, 0);
+        relative.rotate_cube(Z, CounterClockwise);
+        crate::rotate_face(&mut relative, Up, Half, 0);
+        crate::rotate_face(&mut relative, Front, CounterClockwise, 1);
+
+        let mut absolute = crate::RubiksCube::new(3);
+        for (face, movement, depth) in crate::eliminate_rotations(&tokens) {
+            crate::rotate_face(&mut absolute, face, movement, depth);
+        }
+        absolute.rotate_cube(Y, Clockwise);
+        absolute.rotate_cube(Z, CounterClockwise);
+
+        assert_eq!(relative, absolute);
+    }
+
+    #[test]
+    fn slice_moves_follow_their_faces() {
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+        use crate::Slice::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_slice(&mut rc, M, Clockwise);
+        assert_eq!(rc.get(Front, 0, 1), Yellow);
+        assert_eq!(rc.get(Up, 1, 1), Green);
+        assert_eq!(rc.get(Front, 0, 0), Blue);
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_slice(&mut rc, E, Clockwise);
+        assert_eq!(rc.get(Right, 1, 0), Blue);
+        assert_eq!(rc.get(Right, 0, 0), Red);
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_slice(&mut rc, S, Clockwise);
+        assert_eq!(rc.get(Right, 0, 1), Yellow);
+        assert_eq!(rc.get(Up, 1, 0), Orange);
+
+        // M is the middle layer of L, the rest of the cube doesn't move.
+        let mut sliced = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut sliced, Right, Clockwise, 0);
+        crate::rotate_slice(&mut sliced, M, CounterClockwise);
+        let mut layered = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut layered, Right, Clockwise, 0);
+        crate::rotate_face(&mut layered, Left, CounterClockwise, 1);
+        assert_eq!(sliced, layered);
+
+        // Both middle layers turn on an even cube.
+        let mut rc = crate::RubiksCube::new(4);
+        crate::rotate_slice(&mut rc, M, Half);
+        assert_eq!(rc.get(Front, 0, 1), Green);
+        assert_eq!(rc.get(Front, 0, 2), Green);
+        assert_eq!(rc.get(Front, 0, 0), Blue);
+    }
+
+    #[test]
+    fn inner_slice_only_touches_its_band() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(5);
+        for (face, movement) in [(Right, Clockwise), (Up, Clockwise), (Front, Half)] {
+            crate::rotate_face(&mut rc, face, movement, 0);
+        }
+        let before = rc.clone();
+
+        crate::rotate_inner_slice(&mut rc, crate::Axis::X, 1, Clockwise);
+
+        assert_eq!(rc[Left], before[Left]);
+        assert_eq!(rc[Right], before[Right]);
+        for face in [Up, Front, Back, Down] {
+            let col = if matches!(face, Back) { 1 } else { 3 };
+            for row in 0..5 {
+                for c in (0..5).filter(|&c| c != col) {
+                    assert_eq!(rc.get(face, row, c), before.get(face, row, c));
+                }
+            }
+        }
+        assert_eq!(rc.get(Up, 0, 3), before.get(Front, 0, 3));
+        assert_eq!(rc.get(Back, 0, 1), before.get(Up, 4, 3));
+    }
+
+    #[test]
+    #[should_panic]
+    fn inner_slice_rejects_outer_layer() {
+        let mut rc = crate::RubiksCube::new(5);
+        crate::rotate_inner_slice(&mut rc, crate::Axis::Y, 4, crate::Movement::Clockwise);
+    }
+
+    #[test]
+    fn rotate_cube_keeps_state() {
+        use crate::Axis::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        for size in 2..=5 {
+            let mut solved = crate::RubiksCube::new(size);
+            for axis in [X, Y, Z] {
+                for movement in [Clockwise, CounterClockwise, Half] {
+                    solved.rotate_cube(axis, movement);
+                    assert!(solved.is_solved());
+                }
+            }
+
+            let mut rc = crate::RubiksCube::new(size);
+            for (face, movement) in [(Right, Clockwise), (Up, CounterClockwise), (Front, Half)] {
+                crate::rotate_face(&mut rc, face, movement, 0);
+            }
+            let original = rc.clone();
+            for axis in [X, Y, Z] {
+                for _ in 0..4 {
+                    rc.rotate_cube(axis, Clockwise);
+                }
+                assert_eq!(rc, original);
+            }
+        }
+
+        let mut rc = crate::RubiksCube::new(3);
+        rc.rotate_cube(X, Clockwise);
+        assert_eq!(rc.get(Up, 0, 0), crate::Color::Blue);
+    }
+
+    #[test]
+    fn movement_inverse_and_compose() {
+        use crate::Movement::*;
+
+        assert_eq!(Clockwise.inverse(), CounterClockwise);
+        assert_eq!(CounterClockwise.inverse(), Clockwise);
+        assert_eq!(Half.inverse(), Half);
+
+        for (a, b, composed) in [
+            (Clockwise, Clockwise, Some(Half)),
+            (Clockwise, CounterClockwise, None),
+            (Clockwise, Half, Some(CounterClockwise)),
+            (CounterClockwise, Clockwise, None),
+            (CounterClockwise, CounterClockwise, Some(Half)),
+            (CounterClockwise, Half, Some(Clockwise)),
+            (Half, Clockwise, Some(CounterClockwise)),
+            (Half, CounterClockwise, Some(Clockwise)),
+            (Half, Half, None),
+        ] {
+            assert_eq!(a.compose(b), composed, "{a:?} then {b:?}");
+            assert_eq!(a.compose(a.inverse()), None);
+        }
+    }
+
+    #[test]
+    fn equal_up_to_orientation() {
+        use crate::Axis::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        rc.apply_wca("R U2 F' L D").unwrap();
+
+        let mut seen = std::collections::HashSet::new();
+        for turns in 0..64 {
+            let mut turned = rc.clone();
+            for (axis, count) in [(X, turns % 4), (Y, turns / 4 % 4), (Z, turns / 16)] {
+                for _ in 0..count {
+                    turned.rotate_cube(axis, Clockwise);
+                }
+            }
+            assert!(rc.equals_ignoring_orientation(&turned));
+            assert!(turned.equals_ignoring_orientation(&rc));
+            seen.insert(turned);
+        }
+        assert_eq!(seen.len(), 24);
+
+        let turned = crate::moved(&rc, Back, Clockwise, 0);
+        assert!(!rc.equals_ignoring_orientation(&turned));
+        assert!(!rc.equals_ignoring_orientation(&crate::RubiksCube::new(4)));
+    }
+
+    #[test]
+    fn wide_move_turns_outer_layers() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut wide = crate::RubiksCube::new(4);
+        crate::rotate_wide(&mut wide, Right, Clockwise, 2).unwrap();
+        let mut layered = crate::RubiksCube::new(4);
+        crate::rotate_face(&mut layered, Right, Clockwise, 0);
+        crate::rotate_face(&mut layered, Right, Clockwise, 1);
+        assert_eq!(wide, layered);
+
+        let token: crate::MoveToken = "Rw".parse().unwrap();
+        let mut parsed = crate::RubiksCube::new(4);
+        for (face, movement, depth) in crate::eliminate_rotations(&[token]) {
+            crate::rotate_face(&mut parsed, face, movement, depth);
+        }
+        assert_eq!(parsed, layered);
+
+        assert!(crate::rotate_wide(&mut wide, Up, Half, 5).is_err());
+        assert!(crate::rotate_wide(&mut wide, Up, Half, 0).is_err());
+    }
+
+    #[test]
+    fn ascii_net_layout() {
+        let mut rc = crate::RubiksCube::new(2);
+        crate::rotate_face(&mut rc, crate::Face::Right, crate::Movement::Clockwise, 0);
+        assert_eq!(
+            rc.to_ascii_net(),
+            concat!(
+                "  YB\n",
+                "  YB\n",
+                "OOBWRRYG\n",
+                "OOBWRRYG\n",
+                "  WG\n",
+                "  WG\n",
+            )
+        );
+
+        let net = crate::RubiksCube::new(5).to_ascii_net();
+        assert_eq!(net.lines().count(), 15);
+        assert_eq!(net.lines().nth(7), Some("OOOOOBBBBBRRRRRGGGGG"));
+    }
+
+    #[test]
+    fn display_matches_reference_rendering() {
+        use crate::Color::*;
+
+        let paint = |color| {
+            let background = match color {
+                White => yansi::Color::Fixed(255),
+                Yellow => yansi::Color::RGB(255, 255, 0),
+                Red => yansi::Color::RGB(255, 0, 0),
+                Orange => yansi::Color::RGB(255, 100, 0),
+                Blue => yansi::Color::RGB(0, 0, 255),
+                Green => yansi::Color::RGB(0, 140, 0),
+            };
+            yansi::Paint::black("[]").bg(background).to_string()
+        };
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, crate::Face::Right, crate::Movement::Clockwise, 0);
+        crate::rotate_face(&mut rc, crate::Face::Up, crate::Movement::Clockwise, 0);
+
+        let mut expected = String::new();
+        for line in rc.to_ascii_net().lines() {
+            for c in line.chars() {
+                expected += &match c {
+                    ' ' => "  ".to_string(),
+                    'W' => paint(White),
+                    'Y' => paint(Yellow),
+                    'R' => paint(Red),
+                    'O' => paint(Orange),
+                    'B' => paint(Blue),
+                    _ => paint(Green),
+                };
+            }
+            expected.push('\n');
+        }
+
+        assert_eq!(rc.to_string(), expected);
+    }
+
+    #[test]
+    fn identical_cubes_hash_equal() {
+        use std::collections::HashSet;
+        use std::hash::{BuildHasher, RandomState};
+
+        let turned = || {
+            let mut rc = crate::RubiksCube::new(3);
+            crate::rotate_face(&mut rc, crate::Face::Front, crate::Movement::Clockwise, 0);
+            rc
+        };
+
+        let hasher = RandomState::new();
+        assert_eq!(hasher.hash_one(turned()), hasher.hash_one(turned()));
+
+        let set: HashSet<_> = [
+            turned(),
+            crate::RubiksCube::new(3),
+            turned(),
+            crate::RubiksCube::new(3),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&turned()));
+    }
+
+    #[test]
+    fn iterate_over_faces() {
+        use crate::Color::*;
+        use crate::Face::*;
+        use std::collections::HashMap;
+
+        let rc = crate::RubiksCube::new(3);
+        let faces: HashMap<_, _> = (&rc).into_iter().collect();
+        assert_eq!(faces.len(), 6);
+        for (face, color) in [
+            (Up, Yellow),
+            (Left, Orange),
+            (Front, Blue),
+            (Right, Red),
+            (Back, Green),
+            (Down, White),
+        ] {
+            assert_eq!(faces[&face], [color; 9]);
+        }
+
+        let order: Vec<_> = rc.into_iter().map(|(face, _)| face).collect();
+        assert_eq!(order, [Up, Left, Front, Right, Back, Down]);
+    }
+
+    #[test]
+    fn render_with_custom_glyphs() {
+        use crate::Color::*;
+
+        let mut rc = crate::RubiksCube::new(2);
+        crate::rotate_face(&mut rc, crate::Face::Right, crate::Movement::Clockwise, 0);
+        let glyphs = [
+            (White, "w"),
+            (Yellow, "y"),
+            (Red, "r"),
+            (Orange, "o"),
+            (Blue, "b"),
+            (Green, "g"),
+        ];
+        assert_eq!(
+            rc.to_string_with_glyphs(&glyphs),
+            ["  yb", "  yb", "oobwrryg", "oobwrryg", "  wg", "  wg", ""].join("\n")
+        );
+        assert_eq!(
+            rc.to_string_with_glyphs(&glyphs).to_uppercase(),
+            rc.to_ascii_net()
+        );
+
+        let wide = glyphs.map(|(color, glyph)| (color, if color == Blue { "##" } else { glyph }));
+        let net = rc.to_string_with_glyphs(&wide);
+        assert!(net.starts_with("    y ##\n"));
+        assert!(net
+            .lines()
+            .skip(2)
+            .take(2)
+            .all(|line| line == "o o ##w r r y g "));
+    }
+
+    #[test]
+    fn diff_lists_changed_stickers() {
+        use crate::Color::*;
+        use crate::Face::*;
+
+        let solved = crate::RubiksCube::new(3);
+        assert_eq!(solved.diff(&solved.clone()), Ok(vec![]));
+
+        let mut rc = solved.clone();
+        rc.set(Front, 1, 2, Red);
+        rc.set(Up, 0, 0, White);
+        assert_eq!(
+            rc.diff(&solved),
+            Ok(vec![(Up, 0, 0, White, Yellow), (Front, 1, 2, Red, Blue)])
+        );
+
+        let turned = crate::moved(&solved, Right, crate::Movement::Clockwise, 0);
+        assert_eq!(turned.diff(&solved).unwrap().len(), 4 * 3);
+
+        assert!(solved.diff(&crate::RubiksCube::new(2)).is_err());
+    }
+
+    #[test]
+    fn stickers_cover_the_cube() {
+        use crate::Face::*;
+
+        for size in 0..=5 {
+            let mut rc = crate::RubiksCube::new(size);
+            crate::patterns::checkerboard(&mut rc, Default::default());
+            assert_eq!(rc.stickers().count(), 6 * size * size);
+            for (face, row, col, color) in rc.stickers() {
+                assert_eq!(rc.get(face, row, col), color);
+            }
+        }
+
+        let rc = crate::RubiksCube::new(2);
+        let first: Vec<_> = rc
+            .stickers()
+            .take(5)
+            .map(|(f, r, c, _)| (f, r, c))
+            .collect();
+        assert_eq!(
+            first,
+            [(Up, 0, 0), (Up, 0, 1), (Up, 1, 0), (Up, 1, 1), (Left, 0, 0)]
+        );
+    }
+
+    #[test]
+    fn apply_wca_scramble() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        rc.apply_wca("D2 U' R2 U F2 D2 U' R2 U' B' L2 R B R' B R D' U L' R'")
+            .unwrap();
+        assert!(!rc.is_solved());
+        rc.apply_wca("R L U' D R' B' R B' R' L2 B U R2 U D2 F2 U' R2 U D2")
+            .unwrap();
+        assert!(rc.is_solved());
+
+        let mut parsed = crate::RubiksCube::new(4);
+        parsed.apply_wca("  R  Uw2\tF'\n x ").unwrap();
+        let mut turned = crate::RubiksCube::new(4);
+        crate::rotate_face(&mut turned, Right, Clockwise, 0);
+        crate::rotate_wide(&mut turned, Up, Half, 2).unwrap();
+        crate::rotate_face(&mut turned, Front, CounterClockwise, 0);
+        turned.rotate_cube(crate::Axis::X, Clockwise);
+        assert_eq!(parsed, turned);
+
+        let mut rc = crate::RubiksCube::new(3);
+        assert!(rc.apply_wca("R U Q").is_err());
+        assert!(rc.apply_wca("R 4Rw").is_err());
+        assert_eq!(
+            rc.apply_wca("R 4R"),
+            Err("can't turn layer 4 of Right on a 3x3x3 cube".to_string())
+        );
+        assert!(rc.is_solved());
+
+        let mut parsed = crate::RubiksCube::new(5);
+        parsed.apply_wca("3R 2U' 3Rw2").unwrap();
+        let mut turned = crate::RubiksCube::new(5);
+        crate::rotate_face(&mut turned, Right, Clockwise, 2);
+        crate::rotate_face(&mut turned, Up, CounterClockwise, 1);
+        crate::rotate_wide(&mut turned, Right, Half, 3).unwrap();
+        assert_eq!(parsed, turned);
+    }
+
+    #[test]
+    fn sticker_progress() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        assert_eq!(rc.solved_sticker_count(), 54);
+        assert_eq!(rc.face_progress(), [1.0; 6]);
+
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        assert_eq!(rc.solved_sticker_count(), 42);
+        let progress = rc.face_progress();
+        assert_eq!(progress[Left as usize], 1.0);
+        assert_eq!(progress[Up as usize], 6.0 / 9.0);
+
+        let mut rc = crate::RubiksCube::new(4);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        assert_eq!(rc.solved_sticker_count(), 80);
+        assert_eq!(rc.face_progress()[Front as usize], 0.75);
+    }
+
+    #[test]
+    fn center_color_of_even_faces() {
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Right, Clockwise, 1);
+        assert_eq!(rc.center_color(Up), Blue);
+
+        let mut rc = crate::RubiksCube::new(4);
+        assert_eq!(rc.center_color(Front), Blue);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        crate::rotate_face(&mut rc, Right, Clockwise, 1);
+        // Half of the up face is now blue, tied with its own yellow.
+        assert_eq!(rc.center_color(Up), Yellow);
+        for row in 0..4 {
+            for col in 0..2 {
+                rc.set(Up, row, col, Green);
+            }
+        }
+        // Green and blue halves, with no yellow left: blue comes first.
+        assert_eq!(rc.center_color(Up), Blue);
+
+        assert_eq!(crate::RubiksCube::new(0).center_color(Down), White);
+    }
+
+    #[test]
+    fn estimated_moves_remaining_tracks_scrambling() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        for size in 1..=5 {
+            assert_eq!(crate::RubiksCube::new(size).estimated_moves_remaining(), 0);
+        }
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        assert_eq!(rc.estimated_moves_remaining(), 1);
+        crate::rotate_face(&mut rc, Up, Clockwise, 0);
+        let after_two = rc.estimated_moves_remaining();
+        assert!(after_two >= 1);
+
+        rc.apply_wca("F' L2 D B R' U2 L F2 D' B2 R U' L' F")
+            .unwrap();
+        assert!(rc.estimated_moves_remaining() > after_two);
+    }
+
+    #[test]
+    fn try_rotate_face_checks_depth() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        assert_eq!(crate::try_rotate_face(&mut rc, Up, Clockwise, 2), Ok(()));
+        assert_eq!(
+            crate::try_rotate_face(&mut rc, Right, Half, 3),
+            Err(crate::RotateError::DepthOutOfRange {
+                face: Right,
+                depth: 3,
+                size: 3
+            })
+        );
+
+        let mut empty = crate::RubiksCube::new(0);
+        assert_eq!(
+            crate::try_rotate_face(&mut empty, Up, Clockwise, 0),
+            Err(crate::RotateError::EmptyCube)
+        );
+    }
+
+    #[test]
+    fn project_reduced_cube_to_3x3() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let scramble = [
+            (Right, Clockwise),
+            (Up, Half),
+            (Front, CounterClockwise),
+            (Left, Clockwise),
+            (Back, Half),
+            (Down, CounterClockwise),
+        ];
+
+        let mut big = crate::RubiksCube::new(4);
+        let mut small = crate::RubiksCube::new(3);
+        for (face, movement) in scramble {
+            crate::rotate_face(&mut big, face, movement, 0);
+            crate::rotate_face(&mut small, face, movement, 0);
+        }
+
+        let mut projected = big.project_to_3x3().unwrap();
+        assert_eq!(projected, small);
+
+        // Whatever solves the projection solves the reduced cube too.
+        for &(face, movement) in scramble.iter().rev() {
+            crate::rotate_face(&mut projected, face, movement.inverse(), 0);
+            crate::rotate_face(&mut big, face, movement.inverse(), 0);
+        }
+        assert!(projected.is_solved());
+        assert!(big.is_solved());
+
+        crate::rotate_face(&mut big, Right, Clockwise, 1);
+        assert_eq!(big.project_to_3x3(), None);
+        assert_eq!(crate::RubiksCube::new(2).project_to_3x3(), None);
+    }
+
+    #[test]
+    fn moved_leaves_original_alone() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let rc = crate::RubiksCube::new(3);
+        let turned = crate::moved(&rc, Front, Clockwise, 0);
+        assert!(rc.is_solved());
+
+        let mut expected = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut expected, Front, Clockwise, 0);
+        assert_eq!(turned, expected);
+    }
+
+    #[test]
+    fn get_and_set_stickers() {
+        use crate::Color::*;
+        use crate::Face::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        assert_eq!(rc.get(Front, 2, 1), Blue);
+        rc.set(Front, 2, 1, Red);
+        assert_eq!(rc.get(Front, 2, 1), Red);
+        assert_eq!(rc.get(Front, 1, 2), Blue);
+    }
+
+    #[test]
+    #[should_panic(expected = "sticker (0, 3) is off the Front face")]
+    fn get_rejects_off_face_sticker() {
+        crate::RubiksCube::new(3).get(crate::Face::Front, 0, 3);
+    }
+
+    #[test]
+    fn from_faces_checks_dimensions() {
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::RubiksCube;
+
+        let face = |color| vec![vec![color; 2]; 2];
+        let faces = || [Yellow, Orange, Blue, Red, Green, White].map(face);
+        assert_eq!(
+            RubiksCube::from_faces(2, faces()),
+            Ok(RubiksCube::solved(2))
+        );
+
+        let mut scrambled = RubiksCube::new(2);
+        crate::rotate_face(&mut scrambled, Right, crate::Movement::Clockwise, 0);
+        let rows = |face| {
+            (0..2)
+                .map(|row| (0..2).map(|col| scrambled.get(face, row, col)).collect())
+                .collect()
+        };
+        let built = RubiksCube::from_faces(2, crate::Face::ALL.map(rows));
+        assert_eq!(built, Ok(scrambled.clone()));
+
+        assert_eq!(
+            RubiksCube::from_faces(3, faces()),
+            Err("Up face has 2 rows but a 3x3x3 cube needs 3".to_string())
+        );
+        let mut short_row = faces();
+        short_row[2][1].pop();
+        assert_eq!(
+            RubiksCube::from_faces(2, short_row),
+            Err("row 1 of the Front face has 1 stickers but a 2x2x2 cube needs 2".to_string())
+        );
+    }
+
+    #[test]
+    fn apply_moves_matches_rotate_face() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let moves = [
+            crate::Move {
+                face: Right,
+                movement: Clockwise,
+                depth: 0,
+            },
+            crate::Move {
+                face: Up,
+                movement: Half,
+                depth: 1,
+            },
+            crate::Move {
+                face: Front,
+                movement: CounterClockwise,
+                depth: 3,
+            },
+        ];
+
+        let mut expected = crate::RubiksCube::new(4);
+        for m in moves {
+            crate::rotate_face(&mut expected, m.face, m.movement, m.depth);
+        }
+
+        let mut one_by_one = crate::RubiksCube::new(4);
+        for m in moves {
+            one_by_one.apply_move(m);
+        }
+        assert_eq!(one_by_one, expected);
+
+        let mut rc = crate::RubiksCube::new(4);
+        rc.apply_moves(&moves);
+        assert_eq!(rc, expected);
+    }
+
+    #[test]
+    fn rotate_face_n_reduces_to_one_turn() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        for (quarter_turns, movement) in [
+            (1, Some(Clockwise)),
+            (2, Some(Half)),
+            (3, Some(CounterClockwise)),
+            (4, None),
+            (0, None),
+            (-1, Some(CounterClockwise)),
+            (-2, Some(Half)),
+            (-5, Some(CounterClockwise)),
+            (i32::MIN, None),
+            (i32::MAX, Some(CounterClockwise)),
+        ] {
+            let mut rc = crate::RubiksCube::new(4);
+            crate::rotate_face_n(&mut rc, Right, 1, quarter_turns);
+            let mut expected = crate::RubiksCube::new(4);
+            if let Some(movement) = movement {
+                crate::rotate_face(&mut expected, Right, movement, 1);
+            }
+            assert_eq!(rc, expected, "{quarter_turns}");
+        }
+    }
+
+    #[test]
+    fn opposite_and_adjacent_faces() {
+        use crate::Face::*;
+
+        for face in crate::Face::ALL {
+            assert_eq!(face.opposite().opposite(), face);
+            assert_ne!(face.opposite(), face);
+
+            let adjacent = face.adjacent();
+            assert!(!adjacent.contains(&face));
+            assert!(!adjacent.contains(&face.opposite()));
+            assert_eq!(adjacent[0].opposite(), adjacent[2]);
+            assert_eq!(adjacent[1].opposite(), adjacent[3]);
+
+            // A clockwise turn moves a strip of each neighbour's color onto the next neighbour.
+            let mut rc = crate::RubiksCube::new(3);
+            crate::rotate_face(&mut rc, face, crate::Movement::Clockwise, 0);
+            for i in 0..4 {
+                let (from, to) = (adjacent[i], adjacent[(i + 1) % 4]);
+                let color = rc.get(from, 1, 1);
+                let moved = (0..9).filter(|n| rc.get(to, n / 3, n % 3) == color).count();
+                assert_eq!(moved, 3, "{face:?}: {from:?} to {to:?}");
+            }
+        }
+        assert_eq!(Up.adjacent(), [Back, Right, Front, Left]);
+    }
+
+    #[test]
+    fn undo_takes_back_a_scramble() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        for size in 2..=5 {
+            let moves = crate::scramble(size, 30, &mut rng);
+            let mut rc = crate::RubiksCube::new(size);
+            rc.apply_moves(&moves);
+            assert_eq!(rc.history(), moves);
+
+            let mut replay = crate::RubiksCube::new(size);
+            replay.apply_moves(&moves[..29]);
+            assert_eq!(rc.undo(), Some(moves[29]));
+            assert_eq!(rc, replay);
+
+            rc.reset();
+            assert!(rc.is_solved(), "{size}");
+            assert_eq!(rc.history(), []);
+            assert_eq!(rc.undo(), None);
+        }
+
+        // Turns made while recording is off, or with the free functions, aren't undone.
+        let mut rc = crate::RubiksCube::new(3);
+        let r = crate::Move {
+            face: crate::Face::Right,
+            movement: crate::Movement::Clockwise,
+            depth: 0,
+        };
+        rc.set_history_enabled(false);
+        rc.apply_move(r);
+        assert_eq!(rc.undo(), None);
+        rc.set_history_enabled(true);
+        crate::rotate_face(&mut rc, crate::Face::Up, crate::Movement::Half, 0);
+        rc.apply_move(r);
+        assert_eq!(rc.history(), [r]);
+
+        // History doesn't affect equality.
+        let mut unrecorded = crate::RubiksCube::new(3);
+        unrecorded.set_history_enabled(false);
+        crate::rotate_face(
+            &mut unrecorded,
+            crate::Face::Right,
+            crate::Movement::Clockwise,
+            0,
+        );
+        crate::rotate_face(&mut unrecorded, crate::Face::Up, crate::Movement::Half, 0);
+        crate::rotate_face(
+            &mut unrecorded,
+            crate::Face::Right,
+            crate::Movement::Clockwise,
+            0,
+        );
+        assert_eq!(rc, unrecorded);
+    }
+
+    #[test]
+    fn colors_round_trip_through_chars() {
+        use crate::Color::{self, *};
+
+        for color in [White, Yellow, Red, Orange, Blue, Green] {
+            let c = color.to_char();
+            assert!(c.is_ascii_uppercase());
+            assert_eq!(Color::from_char(c), Some(color));
+            assert_eq!(Color::from_char(c.to_ascii_lowercase()), Some(color));
+        }
+        assert_eq!(Orange.to_char(), 'O');
+        assert_eq!(Color::from_char('X'), None);
+        assert_eq!(Color::from_char('U'), None);
+    }
+
+    #[test]
+    fn mirroring_twice_is_the_identity() {
+        use crate::Axis::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(8);
+        for size in 1..=5 {
+            for axis in [X, Y, Z] {
+                let mut solved = crate::RubiksCube::new(size);
+                solved.mirror(axis);
+                assert!(solved.is_solved(), "{size} {axis:?}");
+
+                let mut rc = crate::RubiksCube::new(size);
+                rc.apply_moves(&crate::scramble(size, 20, &mut rng));
+                let original = rc.clone();
+                rc.mirror(axis);
+                if size > 1 {
+                    assert_ne!(rc, original, "{size} {axis:?}");
+                }
+                rc.mirror(axis);
+                assert_eq!(rc, original, "{size} {axis:?}");
+            }
+        }
+
+        // A turn of the original is the reflected face turned the other way on the mirror image.
+        for (axis, swapped) in [(X, [Left, Right]), (Y, [Up, Down]), (Z, [Front, Back])] {
+            for face in crate::Face::ALL {
+                let mut rc = crate::RubiksCube::new(3);
+                crate::rotate_face(&mut rc, Front, Clockwise, 0);
+                crate::rotate_face(&mut rc, Up, Half, 0);
+                let mut image = rc.clone();
+                image.mirror(axis);
+
+                crate::rotate_face(&mut rc, face, Clockwise, 0);
+                rc.mirror(axis);
+                let reflected = if swapped.contains(&face) {
+                    face.opposite()
+                } else {
+                    face
+                };
+                crate::rotate_face(&mut image, reflected, CounterClockwise, 0);
+                assert_eq!(rc, image, "{axis:?} {face:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn solved_faces_lists_complete_faces() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        for size in 1..=4 {
+            let rc = crate::RubiksCube::new(size);
+            assert_eq!(rc.solved_faces(), [Up, Left, Front, Right, Back, Down]);
+        }
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Up, Clockwise, 0);
+        assert_eq!(rc.solved_faces(), [Up, Down]);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        assert_eq!(rc.solved_faces(), []);
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Up, Half, 1);
+        assert_eq!(rc.solved_faces(), [Up, Down]);
+
+        // Faces don't have to be their solved colors to count.
+        let mut rc = crate::RubiksCube::new(3);
+        rc.rotate_cube(crate::Axis::Y, Clockwise);
+        assert_eq!(rc.solved_faces().len(), 6);
+        assert_eq!(rc.get(Front, 0, 0), crate::Color::Red);
+    }
+
+    #[test]
+    fn colors_and_faces_from_u8() {
+        use crate::{Color, Face};
+
+        assert_eq!(Color::try_from(0), Ok(Color::White));
+        assert_eq!(Color::try_from(5), Ok(Color::Green));
+        assert_eq!(
+            Color::try_from(6),
+            Err("6 isn't a color; colors go from 0 to 5".to_string())
+        );
+        assert_eq!(Face::try_from(0), Ok(Face::Up));
+        assert_eq!(Face::try_from(5), Ok(Face::Down));
+        assert_eq!(
+            Face::try_from(6),
+            Err("6 isn't a face; faces go from 0 to 5".to_string())
+        );
+        assert!(Face::try_from(u8::MAX).is_err());
+        for color in crate::COLORS {
+            assert_eq!(Color::try_from(color as u8), Ok(color));
+        }
+    }
+
+    #[test]
+    fn apply_inverse_move_undoes_apply_move() {
+        use crate::Movement::*;
+        use crate::{Move, RubiksCube};
+        use rand::SeedableRng;
+
+        let mut scrambled = RubiksCube::new(4);
+        scrambled.apply_moves(&crate::scramble(
+            4,
+            20,
+            &mut rand::rngs::StdRng::seed_from_u64(560),
+        ));
+        for face in crate::Face::ALL {
+            for movement in [Clockwise, CounterClockwise, Half] {
+                for depth in 0..4 {
+                    let m = Move {
+                        face,
+                        movement,
+                        depth,
+                    };
+                    let mut rc = scrambled.clone();
+                    rc.apply_move(m);
+                    rc.apply_inverse_move(m);
+                    assert_eq!(rc, scrambled, "{m}");
+
+                    let mut inverse = scrambled.clone();
+                    inverse.apply_inverse_move(m);
+                    let mut thrice = scrambled.clone();
+                    thrice.apply_moves(&[m, m, m]);
+                    assert_eq!(inverse, thrice, "{m}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn apply_moves_with_sees_every_state() {
+        use crate::Face::*;
+        use crate::Movement::*;
+        use crate::{Move, RubiksCube};
+
+        let moves = [
+            Move {
+                face: Right,
+                movement: Clockwise,
+                depth: 0,
+            },
+            Move {
+                face: Up,
+                movement: Half,
+                depth: 0,
+            },
+            Move {
+                face: Front,
+                movement: CounterClockwise,
+                depth: 1,
+            },
+        ];
+        let mut seen = Vec::new();
+        let mut rc = RubiksCube::new(3);
+        rc.apply_moves_with(&moves, |rc, m| seen.push((rc.clone(), m)));
+
+        let mut expected = RubiksCube::new(3);
+        assert_eq!(seen.len(), moves.len());
+        for ((state, m), &expected_move) in seen.iter().zip(&moves) {
+            expected.apply_move(expected_move);
+            assert_eq!(*m, expected_move);
+            assert_eq!(*state, expected);
+        }
+        assert_eq!(rc, expected);
+        assert_eq!(rc.history(), moves);
+    }
+
+    #[test]
+    fn faces_and_axes_agree() {
+        use crate::Axis::*;
+        use crate::Face::*;
+
+        for face in crate::Face::ALL {
+            let (positive, negative) = face.axis().faces();
+            assert!(face == positive || face == negative, "{face:?}");
+            assert_eq!(face.opposite().axis(), face.axis());
+            assert_eq!(positive.opposite(), negative);
+        }
+        for axis in [X, Y, Z] {
+            let (positive, negative) = axis.faces();
+            assert_eq!(positive.axis(), axis);
+            assert_eq!(negative.axis(), axis);
+        }
+        assert_eq!(X.faces(), (Right, Left));
+        assert_eq!(Up.axis(), Y);
+        assert_eq!(Back.axis(), Z);
+    }
+
+    #[test]
+    fn stickers_come_in_canonical_order() {
+        use crate::Face::*;
+        use crate::RubiksCube;
+
+        let rc = RubiksCube::new(3);
+        let coords: Vec<_> = rc
+            .stickers()
+            .map(|(face, row, col, _)| (face, row, col))
+            .collect();
+        assert_eq!(coords.len(), 54);
+        assert_eq!(coords[0], (Up, 0, 0));
+        assert_eq!(coords[1], (Up, 0, 1));
+        assert_eq!(coords[3], (Up, 1, 0));
+        assert_eq!(coords[9], (Left, 0, 0));
+        assert_eq!(coords[53], (Down, 2, 2));
+
+        let mut turned = rc.clone();
+        crate::rotate_face(&mut turned, Right, crate::Movement::Clockwise, 0);
+        let diff = rc.diff(&turned).unwrap();
+        let diffed: Vec<_> = diff
+            .iter()
+            .map(|&(face, row, col, ..)| (face, row, col))
+            .collect();
+        let mut sorted = diffed.clone();
+        sorted.sort_by_key(|&(face, row, col)| (face as usize, row, col));
+        assert_eq!(diffed, sorted);
+        assert_eq!(diffed[0], (Up, 0, 2));
+    }
+
+    #[test]
+    fn hamming_distance_counts_differing_stickers() {
+        use crate::RubiksCube;
+        use rand::SeedableRng;
+
+        let solved = RubiksCube::new(3);
+        assert_eq!(solved.hamming_distance(&RubiksCube::new(3)), Ok(0));
+
+        let turned = crate::moved(&solved, crate::Face::Right, crate::Movement::Clockwise, 0);
+        assert_eq!(solved.hamming_distance(&turned), Ok(12));
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(579);
+        let mut scrambled = RubiksCube::new(3);
+        scrambled.apply_moves(&crate::scramble(3, 25, &mut rng));
+        let distance = scrambled.hamming_distance(&solved).unwrap();
+        assert_eq!(distance, scrambled.diff(&solved).unwrap().len());
+        assert_eq!(solved.hamming_distance(&scrambled), Ok(distance));
+        // The six centers never move, and a scramble leaves few other stickers home.
+        assert!((30..=48).contains(&distance), "{distance}");
+
+        assert!(solved.hamming_distance(&RubiksCube::new(4)).is_err());
+    }
+
+    #[test]
+    fn apply_reversed_undoes_moves() {
+        use crate::RubiksCube;
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(583);
+        for size in 1..=6 {
+            let mut start = RubiksCube::new(size);
+            start.apply_moves(&crate::scramble(size, 20, &mut rng));
+            let moves = crate::scramble(size, 30, &mut rng);
+
+            let mut rc = start.clone();
+            rc.apply_moves(&moves);
+            rc.apply_reversed(&moves);
+            assert_eq!(rc, start, "{size}");
+            assert_eq!(rc.history().len(), 20 + 2 * moves.len());
+
+            let mut reversed = start.clone();
+            reversed.apply_reversed(&moves);
+            let mut inverted = start.clone();
+            let inverse = crate::invert(
+                &moves
+                    .iter()
+                    .map(|m| (m.face, m.movement, m.depth))
+                    .collect::<Vec<_>>(),
+            );
+            for (face, movement, depth) in inverse {
+                inverted.apply_move(crate::Move {
+                    face,
+                    movement,
+                    depth,
+                });
+            }
+            assert_eq!(reversed, inverted, "{size}");
+        }
+    }
+
+    #[test]
+    fn infer_move_finds_the_turn() {
+        use crate::Face::*;
+        use crate::Movement::*;
+        use crate::{Move, RubiksCube};
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(578);
+        let mut before = RubiksCube::new(4);
+        before.apply_moves(&crate::scramble(4, 30, &mut rng));
+        for m in crate::scramble(4, 20, &mut rng) {
+            let after = crate::moved(&before, m.face, m.movement, m.depth);
+            let inferred = crate::infer_move(&before, &after).unwrap();
+            assert_eq!(
+                crate::moved(&before, inferred.face, inferred.movement, inferred.depth),
+                after
+            );
+        }
+
+        let after = crate::moved(&before, Right, Clockwise, 3);
+        assert_eq!(
+            crate::infer_move(&before, &after),
+            Some(Move {
+                face: Left,
+                movement: CounterClockwise,
+                depth: 0
+            })
+        );
+
+        assert_eq!(crate::infer_move(&before, &before), None);
+        let mut two_away = after.clone();
+        crate::rotate_face(&mut two_away, Up, Clockwise, 0);
+        assert_eq!(crate::infer_move(&before, &two_away), None);
+        assert_eq!(crate::infer_move(&before, &RubiksCube::new(3)), None);
+    }
+
+    #[test]
+    fn rotate_layers_turns_each_depth() {
+        use crate::Face::*;
+        use crate::Movement::*;
+        use crate::RubiksCube;
+
+        for (size, depths) in [(5, 1..4), (5, 0..2), (4, 2..4), (3, 0..3), (3, 1..1)] {
+            for movement in [Clockwise, CounterClockwise, Half] {
+                let mut together = RubiksCube::new(size);
+                together.apply_wca("R U' F2").unwrap();
+                let mut one_by_one = together.clone();
+                crate::rotate_layers(&mut together, Right, movement, depths.clone());
+                for depth in depths.clone() {
+                    crate::rotate_face(&mut one_by_one, Right, movement, depth);
+                }
+                assert_eq!(together, one_by_one, "{size} {depths:?} {movement:?}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "depth 3 is out of range")]
+    fn rotate_layers_past_the_cube_panics() {
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_layers(&mut rc, crate::Face::Up, crate::Movement::Clockwise, 1..4);
+    }
+
+    #[test]
+    fn far_layer_turns_the_opposite_face() {
+        use crate::Face::*;
+        use crate::Movement::*;
+        use crate::RubiksCube;
+
+        // Turning the layer furthest from a face is turning the opposite face the other way,
+        // stickers on that face included.
+        let mut far = RubiksCube::new(3);
+        crate::rotate_face(&mut far, Up, Clockwise, 2);
+        let mut d_prime = RubiksCube::new(3);
+        d_prime.apply_wca("D'").unwrap();
+        assert_eq!(far, d_prime);
+
+        for size in [2, 3, 4] {
+            for face in crate::Face::ALL {
+                for movement in [Clockwise, CounterClockwise, Half] {
+                    let mut far = RubiksCube::new(size);
+                    far.apply_wca("R U' F2 L").unwrap();
+                    let mut near = far.clone();
+                    crate::rotate_face(&mut far, face, movement, size - 1);
+                    crate::rotate_face(&mut near, face.opposite(), movement.inverse(), 0);
+                    assert_eq!(far, near, "{size} {face:?} {movement:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn snapshot_lists_letters_by_face() {
+        use crate::Face::*;
+
+        let mut rc = crate::RubiksCube::new(2);
+        crate::rotate_face(&mut rc, Right, crate::Movement::Clockwise, 0);
+        let snapshot = rc.snapshot();
+        assert_eq!(snapshot[Up as usize], [['Y', 'B'], ['Y', 'B']]);
+        assert_eq!(snapshot[Down as usize][0], ['W', 'G']);
+        for face in crate::Face::ALL {
+            for (row, letters) in snapshot[face as usize].iter().enumerate() {
+                for (col, &letter) in letters.iter().enumerate() {
+                    assert_eq!(letter, rc.get(face, row, col).to_char());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inner_layers_of_even_cubes() {
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let turned = |size, face, depth| {
+            let mut rc = crate::RubiksCube::new(size);
+            crate::rotate_face(&mut rc, face, Clockwise, depth);
+            rc
+        };
+
+        // The two middle layers of a 4x4x4 are separate layers, each turning on its own.
+        assert_eq!(
+            turned(4, Up, 1).to_ascii_net(),
+            concat!(
+                "    YYYY\n",
+                "    YYYY\n",
+                "    YYYY\n",
+                "    YYYY\n",
+                "OOOOBBBBRRRRGGGG\n",
+                "BBBBRRRRGGGGOOOO\n",
+                "OOOOBBBBRRRRGGGG\n",
+                "OOOOBBBBRRRRGGGG\n",
+                "    WWWW\n",
+                "    WWWW\n",
+                "    WWWW\n",
+                "    WWWW\n",
+            )
+        );
+        assert_eq!(
+            turned(4, Right, 2).to_ascii_net(),
+            concat!(
+                "    YBYY\n",
+                "    YBYY\n",
+                "    YBYY\n",
+                "    YBYY\n",
+                "OOOOBWBBRRRRGGYG\n",
+                "OOOOBWBBRRRRGGYG\n",
+                "OOOOBWBBRRRRGGYG\n",
+                "OOOOBWBBRRRRGGYG\n",
+                "    WGWW\n",
+                "    WGWW\n",
+                "    WGWW\n",
+                "    WGWW\n",
+            )
+        );
+
+        let six = |depth| {
+            let rc = turned(6, Front, depth);
+            let up_rows: Vec<_> = rc[Up].chunks(6).map(|row| row[0]).collect();
+            let down_rows: Vec<_> = rc[Down].chunks(6).map(|row| row[0]).collect();
+            let left_row = &rc[Left][..6];
+            let right_row = &rc[Right][..6];
+            (up_rows, down_rows, left_row.to_vec(), right_row.to_vec())
+        };
+        assert_eq!(
+            six(2),
+            (
+                vec![Yellow, Yellow, Yellow, Orange, Yellow, Yellow],
+                vec![White, White, Red, White, White, White],
+                vec![Orange, Orange, Orange, White, Orange, Orange],
+                vec![Red, Red, Yellow, Red, Red, Red],
+            )
+        );
+        assert_eq!(
+            six(3),
+            (
+                vec![Yellow, Yellow, Orange, Yellow, Yellow, Yellow],
+                vec![White, White, White, Red, White, White],
+                vec![Orange, Orange, White, Orange, Orange, Orange],
+                vec![Red, Red, Red, Yellow, Red, Red],
+            )
+        );
+
+        // Neither middle layer touches the faces it's parallel to, and each undoes cleanly.
+        for size in [4, 6] {
+            let mut middles = crate::RubiksCube::new(size);
+            crate::rotate_face(&mut middles, Front, Clockwise, size / 2 - 1);
+            crate::rotate_face(&mut middles, Front, Clockwise, size / 2);
+            assert_eq!(middles[Front], vec![Blue; size * size]);
+            assert_eq!(middles[Back], vec![Green; size * size]);
+            crate::rotate_face(&mut middles, Front, CounterClockwise, size / 2);
+            crate::rotate_face(&mut middles, Front, CounterClockwise, size / 2 - 1);
+            assert!(middles.is_solved());
+        }
+    }
+
+    #[test]
+    fn index_by_face() {
+        use crate::Color::*;
+        use crate::Face::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        assert_eq!(rc[Front], [Blue; 9]);
+        assert_eq!(rc[Front], rc.faces[Front as usize]);
+
+        rc[Up][4] = Red;
+        assert_eq!(rc.get(Up, 1, 1), Red);
+        rc[Down].fill(Green);
+        assert_eq!(rc.faces[Down as usize], vec![Green; 9]);
+    }
+}
@@ -0,0 +1,252 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+use core::str::FromStr;
+
+use crate::{face_from_letter, face_letter, Axis, Face, Move, MoveToken, Movement};
+
+impl FromStr for Face {
+    type Err = String;
+
+    /// Parses a face from its letter (`U`, `L`, `F`, `R`, `B`, `D`) or its full name, ignoring
+    /// case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(letter), None) = (chars.next(), chars.next()) {
+            if let Some(face) = face_from_letter(letter.to_ascii_uppercase()) {
+                return Ok(face);
+            }
+        }
+        match s.to_ascii_lowercase().as_str() {
+            "up" => Ok(Face::Up),
+            "left" => Ok(Face::Left),
+            "front" => Ok(Face::Front),
+            "right" => Ok(Face::Right),
+            "back" => Ok(Face::Back),
+            "down" => Ok(Face::Down),
+            _ => Err(format!("unknown face {s:?}")),
+        }
+    }
+}
+
+impl Display for Face {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(fmt, "{}", face_letter(*self))
+    }
+}
+
+impl FromStr for Movement {
+    type Err = String;
+
+    /// Parses the suffix of a move: nothing for clockwise, `'` for counter-clockwise and `2` (or
+    /// `2'`) for a half turn.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "" => Ok(Movement::Clockwise),
+            "'" => Ok(Movement::CounterClockwise),
+            "2" | "2'" => Ok(Movement::Half),
+            _ => Err(format!("unknown movement suffix {s:?}")),
+        }
+    }
+}
+
+impl Display for Movement {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        let suffix = match self {
+            Movement::Clockwise => "",
+            Movement::CounterClockwise => "'",
+            Movement::Half => "2",
+        };
+        fmt.write_str(suffix)
+    }
+}
+
+impl FromStr for MoveToken {
+    type Err = String;
+
+    /// Parses a single move in standard notation: a face letter (`R`), a single inner layer
+    /// counted from that face (`3R` for the third layer), a wide move (`Rw`, `3Rw` or lowercase
+    /// `r` for two layers) or a rotation (`x`, `y`, `z`), followed by `'` for a
+    /// counter-clockwise turn or `2` for a half turn.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.chars().take_while(char::is_ascii_digit).count();
+        let (prefix, rest) = s.split_at(digits);
+        let mut chars = rest.chars();
+        let letter = chars
+            .next()
+            .ok_or_else(|| format!("move {s:?} is missing a face letter"))?;
+        let rest = chars.as_str();
+        let (wide, suffix) = match rest.strip_prefix('w') {
+            Some(suffix) => (true, suffix),
+            None => (false, rest),
+        };
+
+        let movement: Movement = suffix
+            .parse()
+            .map_err(|_| format!("move {s:?} has an unknown suffix {suffix:?}"))?;
+
+        let layers = match prefix {
+            "" => None,
+            _ => match prefix.parse::<usize>() {
+                Ok(0) => return Err(format!("move {s:?} has a layer number of 0")),
+                Ok(layers) => Some(layers),
+                Err(e) => return Err(format!("move {s:?} has a bad layer number: {e}")),
+            },
+        };
+
+        let axis = match letter {
+            'x' => Some(Axis::X),
+            'y' => Some(Axis::Y),
+            'z' => Some(Axis::Z),
+            _ => None,
+        };
+        if let Some(axis) = axis {
+            if wide || layers.is_some() {
+                return Err(format!("rotation {s:?} can't be wide"));
+            }
+            return Ok(MoveToken::Rotation(axis, movement));
+        }
+
+        let face = face_from_letter(letter.to_ascii_uppercase())
+            .ok_or_else(|| format!("move {s:?} has an unknown face {letter:?}"))?;
+        if letter.is_ascii_lowercase() {
+            if wide || layers.is_some() {
+                return Err(format!("lowercase move {s:?} can't have a prefix or `w`"));
+            }
+            return Ok(MoveToken::Wide(face, movement, 2));
+        }
+        match (wide, layers) {
+            (true, layers) => Ok(MoveToken::Wide(face, movement, layers.unwrap_or(2))),
+            (false, layer) => Ok(MoveToken::Turn(face, movement, layer.map_or(0, |n| n - 1))),
+        }
+    }
+}
+
+impl Display for MoveToken {
+    /// Writes the move the way [`MoveToken::from_str`] reads it, using the shortest form: `R`
+    /// for the outer layer, `3R` for an inner one, `Rw` for two layers and `3Rw` for more.
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        match *self {
+            MoveToken::Turn(face, movement, 0) => write!(fmt, "{face}{movement}"),
+            MoveToken::Turn(face, movement, depth) => write!(fmt, "{}{face}{movement}", depth + 1),
+            MoveToken::Wide(face, movement, 2) => write!(fmt, "{face}w{movement}"),
+            MoveToken::Wide(face, movement, layers) => write!(fmt, "{layers}{face}w{movement}"),
+            MoveToken::Rotation(axis, movement) => {
+                let axis = match axis {
+                    Axis::X => 'x',
+                    Axis::Y => 'y',
+                    Axis::Z => 'z',
+                };
+                write!(fmt, "{axis}{movement}")
+            }
+        }
+    }
+}
+
+impl Display for Move {
+    /// Writes the move in standard notation, with inner layers numbered from the face: `R`,
+    /// `U'`, `3F2`.
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        MoveToken::Turn(self.face, self.movement, self.depth).fmt(fmt)
+    }
+}
+
+/// Writes `moves` in standard notation, separated by spaces, as [`RubiksCube::apply_wca`]
+/// reads them.
+///
+/// [`RubiksCube::apply_wca`]: crate::RubiksCube::apply_wca
+pub fn sequence_to_string(moves: &[Move]) -> String {
+    moves
+        .iter()
+        .map(Move::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_moves() {
+        use crate::Face::*;
+        use crate::MoveToken::*;
+        use crate::Movement::*;
+
+        let parse = |s: &str| s.parse::<crate::MoveToken>();
+        assert_eq!(parse("R"), Ok(Turn(Right, Clockwise, 0)));
+        assert_eq!(parse("U'"), Ok(Turn(Up, CounterClockwise, 0)));
+        assert_eq!(parse("F2"), Ok(Turn(Front, Half, 0)));
+        assert_eq!(parse("Rw"), Ok(Wide(Right, Clockwise, 2)));
+        assert_eq!(parse("r'"), Ok(Wide(Right, CounterClockwise, 2)));
+        assert_eq!(parse("3Lw2"), Ok(Wide(Left, Half, 3)));
+        assert_eq!(parse("y'"), Ok(Rotation(crate::Axis::Y, CounterClockwise)));
+
+        assert_eq!(parse("3R"), Ok(Turn(Right, Clockwise, 2)));
+        assert_eq!(parse("2U'"), Ok(Turn(Up, CounterClockwise, 1)));
+        assert_eq!(parse("1F2"), Ok(Turn(Front, Half, 0)));
+
+        for bad in ["", "Q", "R3", "3r", "xw", "w", "0R", "0Rw", "3x"] {
+            assert!(parse(bad).is_err(), "{bad:?} should not parse");
+        }
+    }
+
+    #[test]
+    fn faces_and_movements_round_trip() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        for face in crate::Face::ALL {
+            assert_eq!(face.to_string().parse(), Ok(face));
+            assert_eq!(face.to_string().to_lowercase().parse(), Ok(face));
+            assert_eq!(format!("{face:?}").parse(), Ok(face));
+            assert_eq!(format!("{face:?}").to_uppercase().parse(), Ok(face));
+        }
+        assert_eq!(Front.to_string(), "F");
+        assert!("X".parse::<crate::Face>().is_err());
+        assert!("UF".parse::<crate::Face>().is_err());
+
+        for movement in [Clockwise, CounterClockwise, Half] {
+            assert_eq!(movement.to_string().parse(), Ok(movement));
+        }
+        assert_eq!(format!("R{CounterClockwise}"), "R'");
+        assert!("3".parse::<crate::Movement>().is_err());
+    }
+
+    #[test]
+    fn moves_display_as_they_parse() {
+        use crate::Movement::*;
+        use crate::{Face, Move, MoveToken};
+
+        for s in [
+            "R", "U'", "F2", "3R", "2D'", "5B2", "Rw", "Lw'", "3Rw", "4Uw2", "x", "y'", "z2",
+        ] {
+            let token: MoveToken = s.parse().unwrap();
+            assert_eq!(token.to_string(), s);
+            assert_eq!(token.to_string().parse(), Ok(token));
+        }
+        assert_eq!("r'".parse::<MoveToken>().unwrap().to_string(), "Rw'");
+        assert_eq!("1F".parse::<MoveToken>().unwrap().to_string(), "F");
+        assert_eq!("2Rw".parse::<MoveToken>().unwrap().to_string(), "Rw");
+
+        let turn = |face, movement, depth| Move {
+            face,
+            movement,
+            depth,
+        };
+        let moves = [
+            turn(Face::Right, Clockwise, 0),
+            turn(Face::Up, CounterClockwise, 0),
+            turn(Face::Front, Half, 2),
+        ];
+        assert_eq!(crate::sequence_to_string(&moves), "R U' 3F2");
+        assert_eq!(crate::sequence_to_string(&[]), "");
+
+        let mut parsed = crate::RubiksCube::new(4);
+        parsed
+            .apply_wca(&crate::sequence_to_string(&moves))
+            .unwrap();
+        let mut applied = crate::RubiksCube::new(4);
+        applied.apply_moves(&moves);
+        assert_eq!(parsed, applied);
+    }
+}
@@ -0,0 +1,89 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::RubiksCube;
+use crate::COLORS;
+
+const BITS_PER_STICKER: usize = 3;
+
+/// How many bytes [`RubiksCube::to_packed`] writes for a cube of `size`.
+fn packed_len(size: usize) -> usize {
+    (6 * size * size * BITS_PER_STICKER).div_ceil(8)
+}
+
+impl RubiksCube {
+    /// Packs the cube into 3 bits per sticker, in the canonical [`RubiksCube::stickers`] order,
+    /// starting from the lowest bit of the first byte. Any bits left over in
+    /// the last byte are zero, so equal cubes always pack to equal bytes. The size isn't
+    /// stored; pass it back to [`RubiksCube::from_packed`].
+    pub fn to_packed(&self) -> Vec<u8> {
+        let mut bytes = vec![0u8; packed_len(self.size)];
+        for (i, (.., color)) in self.stickers().enumerate() {
+            let bit = i * BITS_PER_STICKER;
+            let code = (color as u16) << (bit % 8);
+            bytes[bit / 8] |= code as u8;
+            if let Some(next) = bytes.get_mut(bit / 8 + 1) {
+                *next |= (code >> 8) as u8;
+            }
+        }
+        bytes
+    }
+
+    /// Reads a cube of `size` packed by [`RubiksCube::to_packed`].
+    pub fn from_packed(size: usize, bytes: &[u8]) -> Result<RubiksCube, String> {
+        if bytes.len() != packed_len(size) {
+            return Err(format!(
+                "a packed {size}x{size}x{size} cube is {} bytes, got {}",
+                packed_len(size),
+                bytes.len()
+            ));
+        }
+
+        let byte = |i: usize| bytes.get(i).copied().unwrap_or(0) as u16;
+        let mut rc = RubiksCube::new(size);
+        for (i, sticker) in rc.faces.iter_mut().flatten().enumerate() {
+            let bit = i * BITS_PER_STICKER;
+            let word = byte(bit / 8) | byte(bit / 8 + 1) << 8;
+            let code = (word >> (bit % 8)) & 0b111;
+            *sticker = *COLORS
+                .get(code as usize)
+                .ok_or_else(|| format!("sticker {i} has no color, its code is {code}"))?;
+        }
+
+        let used_bits = 6 * size * size * BITS_PER_STICKER;
+        if !used_bits.is_multiple_of(8) && bytes[used_bits / 8] >> (used_bits % 8) != 0 {
+            return Err("the padding bits after the last sticker are not zero".to_string());
+        }
+        Ok(rc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn packed_round_trip() {
+        use crate::{rotate_face, Face, Movement, RubiksCube};
+
+        for size in 1..=6 {
+            let mut rc = RubiksCube::new(size);
+            rotate_face(&mut rc, Face::Right, Movement::Clockwise, 0);
+            rotate_face(&mut rc, Face::Up, Movement::CounterClockwise, size / 2);
+            rotate_face(&mut rc, Face::Front, Movement::Half, 0);
+
+            let packed = rc.to_packed();
+            assert_eq!(packed.len(), (18 * size * size).div_ceil(8), "{size}");
+            assert_eq!(RubiksCube::from_packed(size, &packed), Ok(rc), "{size}");
+        }
+
+        assert_eq!(RubiksCube::new(3).to_packed().len(), 21);
+        assert!(RubiksCube::from_packed(3, &[0; 20]).is_err());
+        assert_eq!(
+            RubiksCube::from_packed(1, &[0xff, 0xff, 0x03]),
+            Err("sticker 0 has no color, its code is 7".to_string())
+        );
+        assert!(RubiksCube::from_packed(1, &[0, 0, 0b100]).is_err());
+        assert!(RubiksCube::from_packed(1, &[0, 0, 0]).is_ok());
+    }
+}
@@ -0,0 +1,114 @@
+//! Turning very large cubes on several threads, behind the `parallel` feature.
+
+use rayon::prelude::*;
+
+use crate::{
+    get_sides, position_based_off_corner_and_move_count, Color, Face, Movement, RubiksCube,
+};
+
+/// The smallest cube [`rotate_face`](crate::rotate_face) turns on several threads. Below this
+/// a turn is over before the threads would have started.
+pub const PARALLEL_THRESHOLD: usize = 128;
+
+/// Turns `stickers`, one face laid out row by row, a quarter or half turn in place, each row
+/// filled on its own thread.
+fn rotate_grid(stickers: &mut [Color], size: usize, movement: Movement) {
+    let old = stickers.to_vec();
+    let s = size - 1;
+    stickers
+        .par_chunks_mut(size)
+        .enumerate()
+        .for_each(|(row, stickers)| {
+            for (col, sticker) in stickers.iter_mut().enumerate() {
+                let (from_row, from_col) = match movement {
+                    Movement::Clockwise => (s - col, row),
+                    Movement::CounterClockwise => (col, s - row),
+                    Movement::Half => (s - row, s - col),
+                };
+                *sticker = old[from_row * size + from_col];
+            }
+        });
+}
+
+/// The same turn as the serial `turn_layer`, with each face that changes updated on its own
+/// thread. `depth` must already be checked.
+pub(crate) fn turn_layer(rc: &mut RubiksCube, face: Face, movement: Movement, depth: usize) {
+    let size = rc.size;
+    let sides = get_sides(face);
+    let position = |side: usize, i: usize| {
+        let (row, col) = position_based_off_corner_and_move_count(sides[side].1, i, size, depth);
+        row * size + col
+    };
+    let strips: Vec<Vec<Color>> = (0..4)
+        .map(|side| {
+            let stickers = &rc.faces[sides[side].0 as usize];
+            (0..size).map(|i| stickers[position(side, i)]).collect()
+        })
+        .collect();
+    // Each side takes the strip of the side it turns in from.
+    let shift = match movement {
+        Movement::Clockwise => 3,
+        Movement::CounterClockwise => 1,
+        Movement::Half => 2,
+    };
+
+    rc.faces
+        .par_iter_mut()
+        .enumerate()
+        .for_each(|(index, stickers)| {
+            if index == face as usize && depth == 0 {
+                rotate_grid(stickers, size, movement);
+            } else if index == face.opposite() as usize && depth == size - 1 {
+                rotate_grid(stickers, size, movement.inverse());
+            } else if let Some(side) = sides.iter().position(|side| side.0 as usize == index) {
+                let strip = &strips[(side + shift) % 4];
+                for (i, &color) in strip.iter().enumerate() {
+                    stickers[position(side, i)] = color;
+                }
+            }
+        });
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn matches_serial_turns() {
+        use crate::Movement::*;
+        use crate::{Face, RubiksCube};
+        use rand::SeedableRng;
+
+        for size in [1, 2, 3, 4, 7, 8] {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(size as u64);
+            let mut scrambled = RubiksCube::new(size);
+            scrambled.apply_moves(&crate::scramble(size, 30, &mut rng));
+            for face in Face::ALL {
+                for movement in [Clockwise, CounterClockwise, Half] {
+                    for depth in 0..size {
+                        let mut serial = scrambled.clone();
+                        crate::turn_layer(&mut serial, face, movement, depth);
+                        let mut parallel = scrambled.clone();
+                        super::turn_layer(&mut parallel, face, movement, depth);
+                        assert_eq!(parallel, serial, "{face:?} {movement:?} at depth {depth}");
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn large_scramble_is_reversible() {
+        use crate::RubiksCube;
+        use rand::SeedableRng;
+
+        let size = crate::PARALLEL_THRESHOLD + 1;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(56);
+        let moves = crate::scramble(size, 50, &mut rng);
+        let mut rc = RubiksCube::new(size);
+        rc.apply_moves(&moves);
+        assert!(!rc.is_solved());
+        for m in moves.iter().rev() {
+            crate::rotate_face(&mut rc, m.face, m.movement.inverse(), m.depth);
+        }
+        assert!(rc.is_solved());
+    }
+}
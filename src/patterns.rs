@@ -0,0 +1,365 @@
+//! Ready-made patterns, each made by turning a solved cube.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::{
+    concat_optimized, invert, optimize_sequence, rotate_face, Face, Move, Movement, RubiksCube,
+};
+
+use Face::*;
+use Movement::*;
+
+/// A pattern that [`pattern_moves`] knows how to make.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pattern {
+    Solved,
+    Checkerboard,
+    Superflip,
+    CubeInACube,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern isn't defined for cubes of this size.
+    UnsupportedSize { pattern: Pattern, size: usize },
+}
+
+impl Display for PatternError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            PatternError::UnsupportedSize { pattern, size } => {
+                write!(fmt, "{pattern:?} is not defined on a {size}x{size}x{size}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PatternError {}
+
+// U R2 F B R B2 R U2 L B2 R U' D' R2 F R' L B2 U2 F2
+const SUPERFLIP: [(Face, Movement); 20] = [
+    (Up, Clockwise),
+    (Right, Half),
+    (Front, Clockwise),
+    (Back, Clockwise),
+    (Right, Clockwise),
+    (Back, Half),
+    (Right, Clockwise),
+    (Up, Half),
+    (Left, Clockwise),
+    (Back, Half),
+    (Right, Clockwise),
+    (Up, CounterClockwise),
+    (Down, CounterClockwise),
+    (Right, Half),
+    (Front, Clockwise),
+    (Right, CounterClockwise),
+    (Left, Clockwise),
+    (Back, Half),
+    (Up, Half),
+    (Front, Half),
+];
+
+// F L F U' R U F2 L2 U' L' B D' B' L2 U
+const CUBE_IN_A_CUBE: [(Face, Movement); 15] = [
+    (Front, Clockwise),
+    (Left, Clockwise),
+    (Front, Clockwise),
+    (Up, CounterClockwise),
+    (Right, Clockwise),
+    (Up, Clockwise),
+    (Front, Half),
+    (Left, Half),
+    (Up, CounterClockwise),
+    (Left, CounterClockwise),
+    (Back, Clockwise),
+    (Down, CounterClockwise),
+    (Back, CounterClockwise),
+    (Left, Half),
+    (Up, Clockwise),
+];
+
+/// Which layers [`checkerboard`] turns. Odd cubes come out the same either way, since every
+/// other layer counted from one side is also every other layer counted from the other. Even
+/// cubes can't be given a true checkerboard by turning layers, so each style makes a different
+/// approximation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CheckerboardStyle {
+    /// Every other layer counted in from both outer layers, so the pattern is mirrored about
+    /// the middle of the cube. On even cubes the two middle layers match, leaving a double-wide
+    /// stripe down the middle of each face.
+    #[default]
+    OutsideIn,
+    /// Every other layer counted in from one outer layer, so the stripes alternate all the way
+    /// across. On even cubes this turns the far outer layer too, which makes a checkerboard on
+    /// the up and down faces and evenly alternating stripes on the others.
+    Alternating,
+}
+
+/// Half turns of every other layer of `face`, for `style`.
+fn checkerboard_moves(
+    face: Face,
+    size: usize,
+    style: CheckerboardStyle,
+) -> Vec<(Face, Movement, usize)> {
+    let mut moves = Vec::new();
+    match style {
+        CheckerboardStyle::OutsideIn => {
+            for depth in (1..size.div_ceil(2)).step_by(2) {
+                moves.push((face, Half, depth));
+                if depth != size - depth - 1 {
+                    moves.push((face, Half, size - depth - 1));
+                }
+            }
+        }
+        CheckerboardStyle::Alternating => {
+            moves.extend((1..size).step_by(2).map(|depth| (face, Half, depth)));
+        }
+    }
+    moves
+}
+
+fn checkerboard_pattern(size: usize, style: CheckerboardStyle) -> Vec<(Face, Movement, usize)> {
+    [Right, Up, Front]
+        .into_iter()
+        .flat_map(|face| checkerboard_moves(face, size, style))
+        .collect()
+}
+
+/// The moves that turn a solved cube of `size` into `pattern`.
+pub fn pattern_moves(
+    pattern: Pattern,
+    size: usize,
+) -> Result<Vec<(Face, Movement, usize)>, PatternError> {
+    let outer = |moves: &[(Face, Movement)]| moves.iter().map(|&(f, m)| (f, m, 0)).collect();
+    match pattern {
+        Pattern::Solved => Ok(Vec::new()),
+        Pattern::Checkerboard if size >= 3 => {
+            Ok(checkerboard_pattern(size, CheckerboardStyle::default()))
+        }
+        Pattern::Superflip if size == 3 => Ok(outer(&SUPERFLIP)),
+        Pattern::CubeInACube if size >= 2 => Ok(outer(&CUBE_IN_A_CUBE)),
+        _ => Err(PatternError::UnsupportedSize { pattern, size }),
+    }
+}
+
+impl RubiksCube {
+    /// A new cube of `size` with `pattern` already made on it. The turns that made it aren't
+    /// recorded, so [`RubiksCube::undo`] starts from the pattern. Fails if `pattern` isn't
+    /// defined for `size`, as with [`pattern_moves`].
+    pub fn with_pattern(size: usize, pattern: Pattern) -> Result<RubiksCube, PatternError> {
+        let mut rc = RubiksCube::new(size);
+        for (face, movement, depth) in pattern_moves(pattern, size)? {
+            rotate_face(&mut rc, face, movement, depth);
+        }
+        Ok(rc)
+    }
+}
+
+/// The moves that turn `from` into `to` on a cube of `size`: undo `from`, then make `to`.
+pub fn morph(
+    from: Pattern,
+    to: Pattern,
+    size: usize,
+) -> Result<Vec<(Face, Movement, usize)>, PatternError> {
+    let undo = optimize_sequence(&invert(&pattern_moves(from, size)?));
+    let make = optimize_sequence(&pattern_moves(to, size)?);
+    Ok(concat_optimized(&undo, &make))
+}
+
+/// Makes a checkerboard on every face by half turning every other layer, chosen by `style`, and
+/// returns the turns made. [`Pattern::Checkerboard`] is the [`CheckerboardStyle::OutsideIn`]
+/// version.
+pub fn checkerboard(rc: &mut RubiksCube, style: CheckerboardStyle) -> Vec<Move> {
+    let moves: Vec<_> = checkerboard_pattern(rc.size, style)
+        .into_iter()
+        .map(|(face, movement, depth)| Move {
+            face,
+            movement,
+            depth,
+        })
+        .collect();
+    rc.apply_moves(&moves);
+    moves
+}
+
+/// Flips every edge in place, leaving corners and centers solved. Only defined for 3x3x3 cubes.
+pub fn superflip(rc: &mut RubiksCube) -> Result<(), String> {
+    if rc.size != 3 {
+        return Err(format!(
+            "superflip is only defined on a 3x3x3, not a {0}x{0}x{0}",
+            rc.size
+        ));
+    }
+
+    for (face, movement) in SUPERFLIP {
+        rotate_face(rc, face, movement, 0);
+    }
+    Ok(())
+}
+
+/// Builds a smaller cube of a different color in the up-front-right corner. Only the outer
+/// layers are turned, so on bigger cubes the inner cube stays the same size.
+pub fn cube_in_a_cube(rc: &mut RubiksCube) {
+    for (face, movement) in CUBE_IN_A_CUBE {
+        rotate_face(rc, face, movement, 0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn superflip_flips_every_edge() {
+        let mut rc = crate::RubiksCube::new(3);
+        crate::patterns::superflip(&mut rc).unwrap();
+        assert_eq!(
+            rc.to_facelet_string(),
+            "UBULURUFURURFRBRDRFUFLFRFDFDFDLDRDBDLULBLFLDLBUBRBLBDB"
+        );
+
+        let mut rc = crate::RubiksCube::new(4);
+        assert!(crate::patterns::superflip(&mut rc).is_err());
+        assert!(rc.is_solved());
+    }
+
+    #[test]
+    fn cube_in_a_cube_builds_corner_block() {
+        use crate::Face::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::patterns::cube_in_a_cube(&mut rc);
+
+        // The 2x2 block touching the up-front-right corner on each of those faces.
+        for (face, rows, cols, (far_row, far_col)) in [
+            (Up, 1..3, 1..3, (0, 0)),
+            (Front, 0..2, 1..3, (2, 0)),
+            (Right, 0..2, 0..2, (2, 2)),
+        ] {
+            let block = rc.get(face, rows.start, cols.start);
+            assert_ne!(block, rc.get(face, far_row, far_col));
+            for row in rows {
+                for col in cols.clone() {
+                    assert_eq!(rc.get(face, row, col), block);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn checkerboard_returns_its_moves() {
+        for size in 1..=7 {
+            let mut rc = crate::RubiksCube::new(size);
+            let moves = crate::patterns::checkerboard(&mut rc, Default::default());
+
+            let mut replayed = crate::RubiksCube::new(size);
+            for m in &moves {
+                crate::rotate_face(&mut replayed, m.face, m.movement, m.depth);
+            }
+            assert_eq!(replayed, rc);
+            assert_eq!(moves.is_empty(), size < 3, "{size}");
+        }
+    }
+
+    #[test]
+    fn checkerboard_styles() {
+        use crate::patterns::{checkerboard, CheckerboardStyle::*};
+
+        let make = |size, style| {
+            let mut rc = crate::RubiksCube::new(size);
+            checkerboard(&mut rc, style);
+            rc
+        };
+        for size in [1, 3, 5, 7] {
+            assert_eq!(make(size, OutsideIn), make(size, Alternating), "{size}");
+        }
+
+        assert_eq!(
+            make(4, OutsideIn).to_ascii_net(),
+            "    YWWY\n    WYYW\n    WYYW\n    YWWY\n\
+             ORROBGGBROORGBBG\nROORGBBGORROBGGB\nROORGBBGORROBGGB\nORROBGGBROORGBBG\n\
+             \x20   WYYW\n    YWWY\n    YWWY\n    WYYW\n"
+        );
+        assert_eq!(
+            make(4, Alternating).to_ascii_net(),
+            "    YWYW\n    WYWY\n    YWYW\n    WYWY\n\
+             OOOOGBGBRRRRBGBG\nRRRRGBGBOOOOBGBG\nOOOOGBGBRRRRBGBG\nRRRRGBGBOOOOBGBG\n\
+             \x20   WYWY\n    YWYW\n    WYWY\n    YWYW\n"
+        );
+    }
+
+    #[test]
+    fn morph_checkerboard_to_solved() {
+        use crate::patterns::{morph, pattern_moves, Pattern};
+
+        for size in 3..=7 {
+            let moves = morph(Pattern::Checkerboard, Pattern::Solved, size).unwrap();
+            let checkerboard = pattern_moves(Pattern::Checkerboard, size).unwrap();
+            assert_eq!(moves, crate::invert(&checkerboard));
+
+            let mut rc = crate::RubiksCube::new(size);
+            crate::patterns::checkerboard(&mut rc, Default::default());
+            for (face, movement, depth) in moves {
+                crate::rotate_face(&mut rc, face, movement, depth);
+            }
+            assert!(rc.is_solved(), "{size}");
+        }
+
+        assert_eq!(
+            morph(Pattern::Superflip, Pattern::Solved, 4),
+            Err(crate::patterns::PatternError::UnsupportedSize {
+                pattern: Pattern::Superflip,
+                size: 4
+            })
+        );
+    }
+
+    #[test]
+    fn with_pattern_for_each_size() {
+        use crate::patterns::{pattern_moves, Pattern, PatternError};
+        use crate::RubiksCube;
+
+        let supported = [
+            (Pattern::Solved, 1..=6),
+            (Pattern::Checkerboard, 3..=6),
+            (Pattern::Superflip, 3..=3),
+            (Pattern::CubeInACube, 2..=6),
+        ];
+        for (pattern, sizes) in supported {
+            for size in sizes {
+                let rc = RubiksCube::with_pattern(size, pattern).unwrap();
+                let mut expected = RubiksCube::new(size);
+                for (face, movement, depth) in pattern_moves(pattern, size).unwrap() {
+                    crate::rotate_face(&mut expected, face, movement, depth);
+                }
+                assert_eq!(rc, expected, "{pattern:?} on {size}");
+                assert!(rc.history().is_empty());
+                assert_eq!(
+                    rc.is_solved(),
+                    pattern == Pattern::Solved,
+                    "{pattern:?} on {size}"
+                );
+            }
+        }
+
+        assert_eq!(
+            RubiksCube::with_pattern(5, Pattern::Superflip),
+            Err(PatternError::UnsupportedSize {
+                pattern: Pattern::Superflip,
+                size: 5
+            })
+        );
+        assert!(RubiksCube::with_pattern(1, Pattern::CubeInACube).is_err());
+        for size in [1, 2] {
+            assert_eq!(
+                RubiksCube::with_pattern(size, Pattern::Checkerboard),
+                Err(PatternError::UnsupportedSize {
+                    pattern: Pattern::Checkerboard,
+                    size
+                })
+            );
+        }
+    }
+}
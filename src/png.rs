@@ -0,0 +1,82 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, Rgba, RgbaImage};
+
+use crate::svg::NET_POSITIONS;
+use crate::{ColorScheme, RubiksCube};
+
+/// Sticker borders, and nothing else, are drawn in this.
+const BORDER: Rgba<u8> = Rgba([0, 0, 0, 255]);
+
+impl RubiksCube {
+    /// Draws the cube as a PNG, laid out in the same net as its
+    /// [`Display`](std::fmt::Display) output, with each sticker `cell_px` pixels square and
+    /// outlined in black. The corners of the net left empty are transparent.
+    ///
+    /// Panics if `cell_px` is 0 or the cube has no stickers, as a PNG can't be empty.
+    pub fn to_png(&self, cell_px: u32) -> Vec<u8> {
+        self.to_png_with_scheme(cell_px, &ColorScheme::default())
+    }
+
+    /// Like [`RubiksCube::to_png`], with stickers filled in `scheme`'s colors.
+    pub fn to_png_with_scheme(&self, cell_px: u32, scheme: &ColorScheme) -> Vec<u8> {
+        assert!(
+            cell_px > 0 && self.size > 0,
+            "a PNG needs at least one pixel"
+        );
+        let size = self.size as u32;
+        let face_px = size * cell_px;
+        let mut image = RgbaImage::new(4 * face_px, 3 * face_px);
+        for (face, face_x, face_y) in NET_POSITIONS {
+            for (i, color) in self[face].iter().enumerate() {
+                let x = face_x as u32 * face_px + i as u32 % size * cell_px;
+                let y = face_y as u32 * face_px + i as u32 / size * cell_px;
+                let (r, g, b) = scheme.rgb(*color);
+                for dy in 0..cell_px {
+                    for dx in 0..cell_px {
+                        let on_border = cell_px > 2
+                            && (dx == 0 || dy == 0 || dx == cell_px - 1 || dy == cell_px - 1);
+                        let pixel = if on_border {
+                            BORDER
+                        } else {
+                            Rgba([r, g, b, 255])
+                        };
+                        image.put_pixel(x + dx, y + dy, pixel);
+                    }
+                }
+            }
+        }
+
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .expect("writing a PNG to memory can't fail");
+        png
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn png_is_a_net_of_stickers() {
+        use image::Rgba;
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, crate::Face::Right, crate::Movement::Clockwise, 0);
+        let png = rc.to_png(10);
+        assert!(png.starts_with(b"\x89PNG\r\n\x1a\n"));
+
+        let image = image::load_from_memory(&png).unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (120, 90));
+        // The top right sticker of the front face was brought down from the up face.
+        assert_eq!(*image.get_pixel(55, 35), Rgba([238, 238, 238, 255]));
+        assert_eq!(*image.get_pixel(35, 5), Rgba([255, 255, 0, 255]));
+        assert_eq!(*image.get_pixel(50, 30), Rgba([0, 0, 0, 255]));
+        // Nothing is drawn left of the up face.
+        assert_eq!(image.get_pixel(5, 5)[3], 0);
+
+        let big = crate::RubiksCube::new(7).to_png(3);
+        let image = image::load_from_memory(&big).unwrap();
+        assert_eq!((image.width(), image.height()), (84, 63));
+    }
+}
@@ -0,0 +1,159 @@
+//! Saving moves to a file and playing them back.
+
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::{Move, MoveToken, RubiksCube};
+
+/// Why [`replay_file`] stopped.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The file couldn't be read.
+    Io(io::Error),
+    /// A line held something other than moves this cube can make. Lines count from 1.
+    InvalidLine { line: usize, reason: String },
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Io(e) => write!(fmt, "couldn't read moves: {e}"),
+            ReplayError::InvalidLine { line, reason } => write!(fmt, "line {line}: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReplayError::Io(e) => Some(e),
+            ReplayError::InvalidLine { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(e: io::Error) -> Self {
+        ReplayError::Io(e)
+    }
+}
+
+/// The single layer turns that make `token` on a cube of `size`: one for a turn, one per layer
+/// for a wide turn, and every layer of the face a rotation follows.
+fn token_moves(token: MoveToken, size: usize) -> Result<Vec<Move>, String> {
+    let (face, movement, depths) = match token {
+        MoveToken::Turn(face, movement, depth) if depth < size => {
+            (face, movement, depth..depth + 1)
+        }
+        MoveToken::Turn(face, _, depth) => {
+            return Err(format!(
+                "can't turn layer {} of {face:?} on a {1}x{1}x{1} cube",
+                depth + 1,
+                size
+            ))
+        }
+        MoveToken::Wide(face, movement, layers) if layers <= size => (face, movement, 0..layers),
+        MoveToken::Wide(face, _, layers) => {
+            return Err(format!(
+                "can't turn {layers} layers of {face:?} on a {size}x{size}x{size} cube"
+            ))
+        }
+        MoveToken::Rotation(axis, movement) => (axis.faces().0, movement, 0..size),
+    };
+    Ok(depths
+        .map(|depth| Move {
+            face,
+            movement,
+            depth,
+        })
+        .collect())
+}
+
+/// Reads moves in standard notation from the file at `path`, separated by spaces or newlines,
+/// and makes each one on `rc` as it goes, recorded as by [`RubiksCube::apply_move`]. Wide turns
+/// and rotations are recorded as the layer turns they're made of.
+///
+/// A line is only made once all of it has been read, so an invalid line leaves `rc` with every
+/// line before it made and none of itself.
+pub fn replay_file(rc: &mut RubiksCube, path: &Path) -> Result<(), ReplayError> {
+    let reader = BufReader::new(File::open(path)?);
+    for (i, line) in reader.lines().enumerate() {
+        let invalid = |reason| ReplayError::InvalidLine {
+            line: i + 1,
+            reason,
+        };
+        let mut moves = Vec::new();
+        for token in line?.split_whitespace() {
+            let token = token.parse().map_err(invalid)?;
+            moves.extend(token_moves(token, rc.size).map_err(invalid)?);
+        }
+        rc.apply_moves(&moves);
+    }
+    Ok(())
+}
+
+/// Writes `moves` to the file at `path` in standard notation, one per line, for
+/// [`replay_file`] to read back. A cube's [`history`](RubiksCube::history) saved this way
+/// replays to the same state.
+pub fn save_moves(moves: &[Move], path: &Path) -> io::Result<()> {
+    let mut file = BufWriter::new(File::create(path)?);
+    for m in moves {
+        writeln!(file, "{m}")?;
+    }
+    file.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn saved_history_replays() {
+        use crate::RubiksCube;
+        use rand::SeedableRng;
+
+        let path = std::env::temp_dir().join(format!("rcs-replay-{}.txt", std::process::id()));
+        let mut rng = rand::rngs::StdRng::seed_from_u64(570);
+        let mut rc = RubiksCube::new(4);
+        rc.apply_moves(&crate::scramble(4, 30, &mut rng));
+        crate::save_moves(rc.history(), &path).unwrap();
+
+        let mut replayed = RubiksCube::new(4);
+        crate::replay_file(&mut replayed, &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(replayed, rc);
+        assert_eq!(replayed.history(), rc.history());
+    }
+
+    #[test]
+    fn replay_reports_the_bad_line() {
+        use crate::{ReplayError, RubiksCube};
+
+        let path = std::env::temp_dir().join(format!("rcs-replay-bad-{}.txt", std::process::id()));
+        std::fs::write(&path, "R U\n\nF2 x Rw'\nR Q U\n").unwrap();
+        let mut rc = RubiksCube::new(3);
+        let error = crate::replay_file(&mut rc, &path).unwrap_err();
+        assert!(
+            matches!(&error, ReplayError::InvalidLine { line: 4, .. }),
+            "{error}"
+        );
+
+        let mut expected = RubiksCube::new(3);
+        expected.apply_wca("R U F2 x Rw'").unwrap();
+        assert_eq!(rc, expected);
+        assert_eq!(rc.history().len(), 2 + 1 + 3 + 2);
+
+        std::fs::write(&path, "R 4R\n").unwrap();
+        let error = crate::replay_file(&mut RubiksCube::new(3), &path).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 1: can't turn layer 4 of Right on a 3x3x3 cube"
+        );
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(
+            crate::replay_file(&mut rc, &path),
+            Err(ReplayError::Io(_))
+        ));
+    }
+}
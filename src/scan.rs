@@ -0,0 +1,139 @@
+//! Building a cube from the colors a scanner read off a physical one.
+
+use std::fmt::{Display, Formatter};
+
+use crate::{suggest_scan_fix, Color, Face, RubiksCube, ValidationError};
+
+/// Why [`RubiksCube::from_scan`] rejected a scan.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    /// A face was scanned with the wrong number of rows.
+    RowCount { face: Face, rows: usize },
+    /// A row of a face was scanned with the wrong number of stickers.
+    RowLength {
+        face: Face,
+        row: usize,
+        stickers: usize,
+    },
+    /// The colors don't make a cube that can be solved. `misread` is the sticker that was most
+    /// likely read wrong and the color it probably is, when [`suggest_scan_fix`] finds one.
+    Invalid {
+        error: ValidationError,
+        misread: Option<((Face, usize, usize), Color)>,
+    },
+}
+
+impl Display for ScanError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScanError::RowCount { face, rows } => {
+                write!(fmt, "the {face:?} face was scanned with {rows} rows")
+            }
+            ScanError::RowLength {
+                face,
+                row,
+                stickers,
+            } => write!(
+                fmt,
+                "row {row} of the {face:?} face was scanned with {stickers} stickers"
+            ),
+            ScanError::Invalid { error, misread } => {
+                write!(fmt, "{error}")?;
+                if let Some(((face, row, col), color)) = misread {
+                    write!(
+                        fmt,
+                        "; the sticker at row {row}, column {col} of the {face:?} face is \
+                         probably {color:?}"
+                    )?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ScanError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ScanError::Invalid { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl RubiksCube {
+    /// Builds a cube from six scanned faces, laid out as for [`RubiksCube::from_faces`], and
+    /// checks it with [`RubiksCube::is_valid`] so the result is ready to solve. Unlike
+    /// `from_faces`, a scan of a cube that couldn't be solved is an error.
+    pub fn from_scan(size: usize, scanned: [Vec<Vec<Color>>; 6]) -> Result<RubiksCube, ScanError> {
+        for (i, rows) in scanned.iter().enumerate() {
+            let face = Face::try_from(i as u8).expect("there are six faces");
+            if rows.len() != size {
+                return Err(ScanError::RowCount {
+                    face,
+                    rows: rows.len(),
+                });
+            }
+            if let Some((row, stickers)) = rows.iter().enumerate().find(|(_, r)| r.len() != size) {
+                return Err(ScanError::RowLength {
+                    face,
+                    row,
+                    stickers: stickers.len(),
+                });
+            }
+        }
+        let rc = RubiksCube::from_faces(size, scanned).expect("the rows were just checked");
+        rc.is_valid().map_err(|error| ScanError::Invalid {
+            error,
+            misread: suggest_scan_fix(&rc),
+        })?;
+        Ok(rc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn scans_are_checked() {
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::{RubiksCube, ScanError, ValidationError};
+
+        let mut rc = RubiksCube::new(3);
+        rc.apply_wca("R U F2").unwrap();
+        let scan = |rc: &RubiksCube| {
+            std::array::from_fn(|i| {
+                let face = crate::Face::try_from(i as u8).unwrap();
+                (0..3)
+                    .map(|row| (0..3).map(|col| rc.get(face, row, col)).collect())
+                    .collect()
+            })
+        };
+        assert_eq!(RubiksCube::from_scan(3, scan(&rc)), Ok(rc.clone()));
+
+        let mut short = scan(&rc);
+        short[Back as usize][2].pop();
+        assert_eq!(
+            RubiksCube::from_scan(3, short),
+            Err(ScanError::RowLength {
+                face: Back,
+                row: 2,
+                stickers: 2
+            })
+        );
+        assert_eq!(
+            RubiksCube::from_scan(2, scan(&rc)),
+            Err(ScanError::RowCount { face: Up, rows: 3 })
+        );
+
+        let mut misread = rc.clone();
+        assert_eq!(misread.get(Left, 1, 0), Orange);
+        misread.set(Left, 1, 0, Red);
+        let Err(ScanError::Invalid { error, misread }) = RubiksCube::from_scan(3, scan(&misread))
+        else {
+            panic!("a misread sticker should be invalid");
+        };
+        assert!(matches!(error, ValidationError::ColorCount { .. }));
+        assert_eq!(misread, Some(((Left, 1, 0), Orange)));
+    }
+}
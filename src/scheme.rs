@@ -0,0 +1,185 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::COLORS;
+use crate::{solved_color, Color, Face, RubiksCube};
+
+/// How a cube looks: the RGB each sticker color is drawn in, and the color each face has when
+/// the cube is solved. The default is the scheme every cube uses unless told otherwise.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColorScheme {
+    /// Indexed by `Color as usize`.
+    rgb: [(u8, u8, u8); 6],
+    /// Indexed by `Face as usize`.
+    faces: [Color; 6],
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        ColorScheme {
+            rgb: COLORS.map(Color::rgb),
+            faces: Face::ALL.map(solved_color),
+        }
+    }
+}
+
+impl ColorScheme {
+    /// The RGB stickers of `color` are drawn in.
+    pub fn rgb(&self, color: Color) -> (u8, u8, u8) {
+        self.rgb[color as usize]
+    }
+
+    /// Draws stickers of `color` in `rgb` instead.
+    pub fn with_rgb(mut self, color: Color, rgb: (u8, u8, u8)) -> ColorScheme {
+        self.rgb[color as usize] = rgb;
+        self
+    }
+
+    /// The color of `face` on a solved cube.
+    pub fn solved_color(&self, face: Face) -> Color {
+        self.faces[face as usize]
+    }
+
+    /// Gives each face, in [`Face`] order, its color on a solved cube. Fails unless every color
+    /// is used exactly once.
+    pub fn with_face_colors(mut self, faces: [Color; 6]) -> Result<ColorScheme, String> {
+        if let Some(&missing) = COLORS.iter().find(|color| !faces.contains(color)) {
+            return Err(format!(
+                "no face is {missing:?}; every color needs its own face"
+            ));
+        }
+        self.faces = faces;
+        Ok(self)
+    }
+}
+
+/// Whether stickers print as colored blocks rather than letters. Terminal colors need the
+/// `std` feature, so without it they're always off.
+#[cfg(feature = "std")]
+pub(crate) fn colors_enabled() -> bool {
+    yansi::Paint::is_enabled()
+}
+
+#[cfg(not(feature = "std"))]
+pub(crate) fn colors_enabled() -> bool {
+    false
+}
+
+/// Writes `text` in black on a block of `rgb`, for when [`colors_enabled`].
+#[cfg(feature = "std")]
+fn paint(fmt: &mut Formatter<'_>, text: impl Display, rgb: (u8, u8, u8)) -> core::fmt::Result {
+    let background = match rgb {
+        // The default white, from the terminal's palette.
+        rgb if rgb == Color::White.rgb() => yansi::Color::Fixed(255),
+        (r, g, b) => yansi::Color::RGB(r, g, b),
+    };
+    write!(fmt, "{}", yansi::Paint::black(text).bg(background))
+}
+
+#[cfg(not(feature = "std"))]
+fn paint(_: &mut Formatter<'_>, _: impl Display, _: (u8, u8, u8)) -> core::fmt::Result {
+    unreachable!("terminal colors need the `std` feature")
+}
+
+/// Writes one sticker of `color`: a block painted `rgb` when terminal colors are on, or its
+/// bracketed letter when they're off.
+pub(crate) fn write_sticker(
+    fmt: &mut Formatter<'_>,
+    color: Color,
+    rgb: (u8, u8, u8),
+) -> core::fmt::Result {
+    if colors_enabled() {
+        paint(fmt, "[]", rgb)
+    } else {
+        write!(fmt, "[{}]", color.to_char())
+    }
+}
+
+/// Writes a supercube center like [`write_sticker`], with an arrow in place of the brackets
+/// pointing the way the sticker does: `orientation` quarter turns clockwise from up.
+pub(crate) fn write_marked_sticker(
+    fmt: &mut Formatter<'_>,
+    color: Color,
+    rgb: (u8, u8, u8),
+    orientation: u8,
+) -> core::fmt::Result {
+    let arrow = ['↑', '→', '↓', '←'][orientation as usize % 4];
+    if colors_enabled() {
+        paint(fmt, format_args!("{arrow} "), rgb)
+    } else {
+        write!(fmt, "{}{arrow} ", color.to_char())
+    }
+}
+
+/// A cube printed with a [`ColorScheme`]. See [`RubiksCube::display_with`].
+struct WithScheme<'a>(&'a RubiksCube, &'a ColorScheme);
+
+impl Display for WithScheme<'_> {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> core::fmt::Result {
+        self.0.write_net(fmt, self.1)
+    }
+}
+
+impl RubiksCube {
+    /// A solved cube of `size` with each face the color `scheme` gives it.
+    ///
+    /// Solvers and [`RubiksCube::is_valid`] expect the default face colors, so cubes built
+    /// with other face colors are best kept to display.
+    pub fn new_with_scheme(size: usize, scheme: &ColorScheme) -> RubiksCube {
+        RubiksCube {
+            size,
+            faces: Face::ALL.map(|face| vec![scheme.solved_color(face); size * size]),
+            history: Some(Vec::new()),
+            orientations: None,
+        }
+    }
+
+    /// Prints the same net as `Display`, with stickers painted in `scheme`'s colors.
+    pub fn display_with<'a>(&'a self, scheme: &'a ColorScheme) -> impl Display + 'a {
+        WithScheme(self, scheme)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn default_scheme_matches_plain_output() {
+        use crate::{Color, ColorScheme, Face, RubiksCube};
+
+        let scheme = ColorScheme::default();
+        let mut rc = RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Face::Right, crate::Movement::Clockwise, 0);
+        assert_eq!(rc.display_with(&scheme).to_string(), rc.to_string());
+        assert_eq!(rc.to_svg_with_scheme(&scheme), rc.to_svg());
+        assert_eq!(RubiksCube::new_with_scheme(4, &scheme), RubiksCube::new(4));
+
+        let recolored = scheme.clone().with_rgb(Color::Green, (0, 200, 80));
+        assert_eq!(recolored.rgb(Color::Green), (0, 200, 80));
+        assert_eq!(recolored.rgb(Color::Blue), scheme.rgb(Color::Blue));
+        assert!(rc
+            .to_svg_with_scheme(&recolored)
+            .contains("fill=\"rgb(0,200,80)\""));
+    }
+
+    #[test]
+    fn face_colors_must_each_be_used_once() {
+        use crate::Color::*;
+        use crate::{ColorScheme, Face, RubiksCube};
+
+        let swapped = ColorScheme::default()
+            .with_face_colors([White, Orange, Blue, Red, Green, Yellow])
+            .unwrap();
+        assert_eq!(swapped.solved_color(Face::Up), White);
+        let rc = RubiksCube::new_with_scheme(2, &swapped);
+        assert!(rc.is_solved());
+        assert_eq!(rc.get(Face::Down, 1, 1), Yellow);
+
+        assert_eq!(
+            ColorScheme::default().with_face_colors([White, Orange, Blue, Red, Green, White]),
+            Err("no face is Yellow; every color needs its own face".to_string())
+        );
+    }
+}
@@ -0,0 +1,339 @@
+use rand::Rng;
+
+use crate::{rotate_face, Face, Move, Movement, RubiksCube};
+
+use Face::*;
+use Movement::*;
+
+/// Algorithms that only change the last layer, leaving the first two layers solved.
+const LAST_LAYER_ALGORITHMS: [&[(Face, Movement)]; 5] = [
+    // Sune
+    &[
+        (Right, Clockwise),
+        (Up, Clockwise),
+        (Right, CounterClockwise),
+        (Up, Clockwise),
+        (Right, Clockwise),
+        (Up, Half),
+        (Right, CounterClockwise),
+    ],
+    // Anti-Sune
+    &[
+        (Right, Clockwise),
+        (Up, Half),
+        (Right, CounterClockwise),
+        (Up, CounterClockwise),
+        (Right, Clockwise),
+        (Up, CounterClockwise),
+        (Right, CounterClockwise),
+    ],
+    // T-perm
+    &[
+        (Right, Clockwise),
+        (Up, Clockwise),
+        (Right, CounterClockwise),
+        (Up, CounterClockwise),
+        (Right, CounterClockwise),
+        (Front, Clockwise),
+        (Right, Half),
+        (Up, CounterClockwise),
+        (Right, CounterClockwise),
+        (Up, CounterClockwise),
+        (Right, Clockwise),
+        (Up, Clockwise),
+        (Right, CounterClockwise),
+        (Front, CounterClockwise),
+    ],
+    // Ua-perm
+    &[
+        (Right, Clockwise),
+        (Up, CounterClockwise),
+        (Right, Clockwise),
+        (Up, Clockwise),
+        (Right, Clockwise),
+        (Up, Clockwise),
+        (Right, Clockwise),
+        (Up, CounterClockwise),
+        (Right, CounterClockwise),
+        (Up, CounterClockwise),
+        (Right, Half),
+    ],
+    // Edge orientation: F R U R' U' F'
+    &[
+        (Front, Clockwise),
+        (Right, Clockwise),
+        (Up, Clockwise),
+        (Right, CounterClockwise),
+        (Up, CounterClockwise),
+        (Front, CounterClockwise),
+    ],
+];
+
+/// Builds a cube with only its last (up) layer scrambled, for drilling last-layer algorithms.
+///
+/// A few random last-layer algorithms are applied to a solved cube, each after a random turn of
+/// the up face. Only outer layers are turned, so bigger cubes get the same treatment.
+pub fn scramble_last_layer_only(size: usize, rng: &mut impl Rng) -> RubiksCube {
+    let mut rc = RubiksCube::new(size);
+    for _ in 0..rng.gen_range(3..=6) {
+        let setup = [Clockwise, CounterClockwise, Half][rng.gen_range(0..3)];
+        rotate_face(&mut rc, Up, setup, 0);
+        let algorithm = LAST_LAYER_ALGORITHMS[rng.gen_range(0..LAST_LAYER_ALGORITHMS.len())];
+        for &(face, movement) in algorithm {
+            rotate_face(&mut rc, face, movement, 0);
+        }
+    }
+    rc
+}
+
+/// How mixed the cube's colors are: the Shannon entropy (in bits) of each face's colors,
+/// averaged over the six faces. A solved cube scores 0 and the ceiling is `log2(6)`.
+pub fn scramble_entropy(rc: &RubiksCube) -> f64 {
+    let stickers = (rc.size * rc.size) as f64;
+    let total: f64 = rc
+        .faces
+        .iter()
+        .map(|face| {
+            let mut counts = [0usize; 6];
+            for &color in face {
+                counts[color as usize] += 1;
+            }
+            counts
+                .iter()
+                .filter(|&&count| count > 0)
+                .map(|&count| {
+                    let p = count as f64 / stickers;
+                    -p * p.log2()
+                })
+                .sum::<f64>()
+        })
+        .sum();
+    total / 6.0
+}
+
+fn random_turn(size: usize, rng: &mut impl Rng) -> (Face, Movement, usize) {
+    let face = num_traits::FromPrimitive::from_usize(rng.gen_range(0..6)).unwrap();
+    let movement = [Clockwise, CounterClockwise, Half][rng.gen_range(0..3)];
+    (face, movement, rng.gen_range(0..size))
+}
+
+/// A scramble is picked again if simplifying it leaves fewer than this percentage of its turns.
+const MIN_EFFECTIVE_DEPTH_PERCENT: usize = 75;
+
+/// How many turns `moves` really makes: its length once turns of the same layer are merged and
+/// cancelled by [`simplify`](crate::simplify), whether they're neighbours or only have turns
+/// about the same axis between them. `R R'` makes none, and `R L R'` only one, the `L`.
+pub fn effective_scramble_depth(moves: &[Move]) -> usize {
+    crate::simplify(moves).len()
+}
+
+/// Picks `count` random turns of any layer of a cube of `size`.
+///
+/// Turns of the same layer merge or cancel when nothing but turns about the same axis comes
+/// between them, so random turns can be a much shallower scramble than they look. Any scramble
+/// whose [effective depth](effective_scramble_depth) is under three quarters of `count` is
+/// thrown away and picked again.
+pub fn scramble(size: usize, count: usize, rng: &mut impl Rng) -> Vec<Move> {
+    loop {
+        let moves: Vec<_> = (0..count)
+            .map(|_| {
+                let (face, movement, depth) = random_turn(size, rng);
+                Move {
+                    face,
+                    movement,
+                    depth,
+                }
+            })
+            .collect();
+        if effective_scramble_depth(&moves) * 100 >= count * MIN_EFFECTIVE_DEPTH_PERCENT {
+            return moves;
+        }
+    }
+}
+
+/// A WCA-style scramble for a 3x3x3: moves from solved to a [random state]
+/// (RubiksCube::random_state), found by undoing a [Kociemba](RubiksCube::solve_kociemba)
+/// solution of it. Every solvable state is equally likely, which no number of random turns
+/// quite manages.
+pub fn random_state_scramble(rng: &mut impl Rng) -> Vec<Move> {
+    let state = RubiksCube::random_state(rng);
+    let solution = state
+        .solve_kociemba(30)
+        .expect("two-phase solutions never need more than 30 moves");
+    solution
+        .iter()
+        .rev()
+        .map(|m| Move {
+            movement: m.movement.inverse(),
+            ..*m
+        })
+        .collect()
+}
+
+pub const MAX_ENTROPY_SCRAMBLE_MOVES: usize = 1000;
+
+/// Scrambles a solved cube with random turns until [`scramble_entropy`] reaches `target`,
+/// giving up after [`MAX_ENTROPY_SCRAMBLE_MOVES`] turns. Returns the turns made.
+pub fn scramble_to_entropy(
+    size: usize,
+    target: f64,
+    rng: &mut impl Rng,
+) -> Vec<(Face, Movement, usize)> {
+    let mut rc = RubiksCube::new(size);
+    let mut moves = Vec::new();
+    while scramble_entropy(&rc) < target && moves.len() < MAX_ENTROPY_SCRAMBLE_MOVES {
+        let (face, movement, depth) = random_turn(size, rng);
+        rotate_face(&mut rc, face, movement, depth);
+        moves.push((face, movement, depth));
+    }
+    moves
+}
+
+/// Scrambles a solved cube of `size` with `count` random turns of any layer, seeded by `seed`,
+/// then undoes them. Every turn is a permutation of the stickers, so this should always give
+/// back a solved cube; tests of anything that turns layers can check theirs against it.
+#[cfg(test)]
+pub(crate) fn scramble_and_undo(size: usize, count: usize, seed: u64) -> RubiksCube {
+    use rand::SeedableRng;
+
+    let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
+    let moves: Vec<_> = scramble(size, count, &mut rng)
+        .into_iter()
+        .map(|m| (m.face, m.movement, m.depth))
+        .collect();
+
+    let mut rc = RubiksCube::new(size);
+    for (face, movement, depth) in moves.iter().chain(&crate::invert(&moves)) {
+        rotate_face(&mut rc, *face, *movement, *depth);
+    }
+    rc
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    #[test]
+    fn random_turns_are_reversible() {
+        use super::scramble_and_undo;
+
+        for size in 2..=8 {
+            for seed in 0..100 {
+                assert_eq!(
+                    scramble_and_undo(size, 40, seed),
+                    crate::RubiksCube::new(size),
+                    "size {size}, seed {seed}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn last_layer_scramble_keeps_first_two_layers() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(7);
+        for size in [2, 3, 4] {
+            for _ in 0..20 {
+                let rc = crate::scramble_last_layer_only(size, &mut rng);
+                assert!(rc.is_solved_except(&[crate::Face::Up]));
+            }
+        }
+    }
+
+    #[test]
+    fn is_solved_except_ignores_only_that_layer() {
+        use crate::Face::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, Up, crate::Movement::Clockwise, 0);
+        assert!(!rc.is_solved());
+        assert!(rc.is_solved_except(&[Up]));
+        assert!(!rc.is_solved_except(&[Down]));
+
+        crate::rotate_face(&mut rc, Right, crate::Movement::Clockwise, 0);
+        assert!(!rc.is_solved_except(&[Up]));
+        assert!(rc.is_solved_except(&[Up, Right]));
+    }
+
+    #[test]
+    fn scramble_to_entropy_reaches_target() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(3);
+        assert_eq!(crate::scramble_entropy(&crate::RubiksCube::new(3)), 0.0);
+
+        for (size, target) in [(2, 1.2), (3, 1.8), (5, 2.0)] {
+            let moves = crate::scramble_to_entropy(size, target, &mut rng);
+            let mut rc = crate::RubiksCube::new(size);
+            for (face, movement, depth) in moves {
+                crate::rotate_face(&mut rc, face, movement, depth);
+            }
+            assert!(crate::scramble_entropy(&rc) >= target);
+        }
+
+        let moves = crate::scramble_to_entropy(3, 10.0, &mut rng);
+        assert_eq!(moves.len(), crate::MAX_ENTROPY_SCRAMBLE_MOVES);
+    }
+
+    #[test]
+    fn scramble_picks_layers_of_the_cube() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(9);
+        let moves = crate::scramble(4, 50, &mut rng);
+        assert_eq!(moves.len(), 50);
+        assert!(moves.iter().all(|m| m.depth < 4));
+        assert!(moves.iter().any(|m| m.depth > 0));
+    }
+
+    #[test]
+    fn random_states_are_valid() {
+        use std::collections::HashSet;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(558);
+        let states: HashSet<_> = (0..500)
+            .map(|_| crate::RubiksCube::random_state(&mut rng))
+            .inspect(|rc| assert_eq!(rc.is_valid(), Ok(())))
+            .collect();
+        assert_eq!(states.len(), 500);
+    }
+
+    #[test]
+    fn random_state_scramble_reaches_the_state() {
+        let state = crate::RubiksCube::random_state(&mut rand::rngs::StdRng::seed_from_u64(7));
+        let moves = crate::random_state_scramble(&mut rand::rngs::StdRng::seed_from_u64(7));
+        assert!(moves.len() <= 30);
+
+        let mut rc = crate::RubiksCube::new(3);
+        rc.apply_moves(&moves);
+        assert_eq!(rc, state);
+    }
+
+    #[test]
+    fn scrambles_are_nearly_as_deep_as_asked() {
+        use crate::Face::*;
+        use crate::Move;
+        use crate::Movement::*;
+
+        let r = |movement| Move {
+            face: Right,
+            movement,
+            depth: 0,
+        };
+        assert_eq!(crate::effective_scramble_depth(&[]), 0);
+        assert_eq!(
+            crate::effective_scramble_depth(&[r(Clockwise), r(CounterClockwise)]),
+            0
+        );
+        assert_eq!(
+            crate::effective_scramble_depth(&[r(Clockwise), r(Clockwise), r(Half)]),
+            0
+        );
+        assert_eq!(crate::effective_scramble_depth(&[r(Half)]), 1);
+
+        // Small cubes have few layers to pick from, so their random turns collapse most often.
+        for size in [1, 2, 3] {
+            let mut rng = rand::rngs::StdRng::seed_from_u64(563);
+            for count in [1, 2, 6, 20] {
+                let moves = crate::scramble(size, count, &mut rng);
+                assert_eq!(moves.len(), count);
+                assert!(crate::effective_scramble_depth(&moves) * 4 >= count * 3);
+            }
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use alloc::vec::Vec;
+
+use crate::{Face, Move, Movement};
+
+/// A way of counting the length of an algorithm.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Metric {
+    /// Half turn metric: any outer layer turn counts as 1.
+    Htm,
+    /// Quarter turn metric: outer quarter turns count as 1 and half turns as 2.
+    Qtm,
+    /// Slice turn metric: any single layer turn, inner slices included, counts as 1.
+    Stm,
+}
+
+/// Pushes `next` onto `moves`, merging it into an earlier turn of the same layer when only turns
+/// about the same axis come after that turn. Parallel layers commute, so `R L R'` is `R R' L`,
+/// which is just `L`. Returns whether it was merged.
+///
+/// A move whose merge cancels completely is removed, which can only shorten the run of same-axis
+/// turns at the end, so pushing a whole sequence one move at a time already reaches a fixed point.
+fn push_merging(moves: &mut Vec<(Face, Movement, usize)>, next: (Face, Movement, usize)) -> bool {
+    let (face, movement, depth) = next;
+    let parallel = moves
+        .iter()
+        .rev()
+        .take_while(|m| m.0.axis() == face.axis())
+        .count();
+    let start = moves.len() - parallel;
+    match moves[start..]
+        .iter()
+        .position(|m| m.0 == face && m.2 == depth)
+    {
+        Some(i) => {
+            match moves[start + i].1.compose(movement) {
+                Some(merged) => moves[start + i].1 = merged,
+                None => {
+                    moves.remove(start + i);
+                }
+            }
+            true
+        }
+        None => {
+            moves.push(next);
+            false
+        }
+    }
+}
+
+/// Counts `moves` in `metric`. Moves deeper than the outer layer are inner slice turns, which
+/// HTM and QTM count as the two outer turns they stand for.
+pub fn metric_count(moves: &[Move], metric: Metric) -> usize {
+    moves
+        .iter()
+        .map(|m| {
+            let outer_turns = if m.depth == 0 { 1 } else { 2 };
+            match metric {
+                Metric::Htm => outer_turns,
+                Metric::Qtm => match m.movement {
+                    Movement::Half => 2 * outer_turns,
+                    _ => outer_turns,
+                },
+                Metric::Stm => 1,
+            }
+        })
+        .sum()
+}
+
+/// Merges turns of the same layer that are consecutive or only separated by turns about the same
+/// axis, dropping any that cancel out completely.
+pub fn optimize_sequence(moves: &[(Face, Movement, usize)]) -> Vec<(Face, Movement, usize)> {
+    let mut optimized = Vec::with_capacity(moves.len());
+    for &m in moves {
+        push_merging(&mut optimized, m);
+    }
+    optimized
+}
+
+/// The moves that undo `moves`: each turned the other way, in reverse order.
+pub fn invert(moves: &[(Face, Movement, usize)]) -> Vec<(Face, Movement, usize)> {
+    moves
+        .iter()
+        .rev()
+        .map(|&(face, movement, depth)| (face, movement.inverse(), depth))
+        .collect()
+}
+
+/// Like [`optimize_sequence`], for [`Move`]s: merges turns of the same layer, with turns that
+/// cancel out dropped, until no layer is turned twice in a row of turns about one axis.
+pub fn simplify(moves: &[Move]) -> Vec<Move> {
+    let mut simplified = Vec::with_capacity(moves.len());
+    for m in moves {
+        push_merging(&mut simplified, (m.face, m.movement, m.depth));
+    }
+    simplified
+        .into_iter()
+        .map(|(face, movement, depth)| Move {
+            face,
+            movement,
+            depth,
+        })
+        .collect()
+}
+
+/// Appends `b` to `a`, merging turns across the join. Only the moves around the join are
+/// looked at, so each half is assumed to already be optimized.
+///
+/// Moves of `b` are merged in one at a time for as long as they turn about the axis of the
+/// run of turns at the end of the sequence so far. The first move about another axis starts a
+/// new run that nothing in `a` can reach, so the rest of `b` is copied as it is.
+pub fn concat_optimized(
+    a: &[(Face, Movement, usize)],
+    b: &[(Face, Movement, usize)],
+) -> Vec<(Face, Movement, usize)> {
+    let mut joined = Vec::with_capacity(a.len() + b.len());
+    joined.extend_from_slice(a);
+    for (i, &m) in b.iter().enumerate() {
+        if joined.last().is_none_or(|last| last.0.axis() != m.0.axis()) {
+            joined.extend_from_slice(&b[i..]);
+            break;
+        }
+        push_merging(&mut joined, m);
+    }
+    joined
+}
+
+/// The moves that undo `moves`, as [`invert`] does for tuples.
+fn inverse_moves(moves: &[Move]) -> impl Iterator<Item = Move> + '_ {
+    moves.iter().rev().map(|&m| Move {
+        movement: m.movement.inverse(),
+        ..m
+    })
+}
+
+/// The commutator `[a, b]`: `a`, then `b`, then `a` undone, then `b` undone. Nothing is merged,
+/// so the result is exactly the four parts end to end.
+pub fn commutator(a: &[Move], b: &[Move]) -> Vec<Move> {
+    a.iter()
+        .chain(b)
+        .copied()
+        .chain(inverse_moves(a))
+        .chain(inverse_moves(b))
+        .collect()
+}
+
+/// The conjugate `[setup: alg]`: `setup`, then `alg`, then `setup` undone, so `alg` acts on
+/// whatever pieces `setup` brought into its place. Nothing is merged.
+pub fn conjugate(setup: &[Move], alg: &[Move]) -> Vec<Move> {
+    setup
+        .iter()
+        .chain(alg)
+        .copied()
+        .chain(inverse_moves(setup))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn concat_cancels_at_the_join() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let r = (Right, Clockwise, 0);
+        let r_prime = (Right, CounterClockwise, 0);
+        assert_eq!(crate::concat_optimized(&[r], &[r]), [(Right, Half, 0)]);
+        assert_eq!(crate::concat_optimized(&[r], &[r_prime]), []);
+
+        let u = (Up, Clockwise, 0);
+        let u_prime = (Up, CounterClockwise, 0);
+        assert_eq!(
+            crate::concat_optimized(&[r, u], &[u_prime, r, r]),
+            [(Right, CounterClockwise, 0)]
+        );
+        assert_eq!(
+            crate::concat_optimized(&[r, u], &[u_prime, r_prime, u]),
+            [u]
+        );
+        assert_eq!(
+            crate::concat_optimized(&[r], &[(Right, Clockwise, 1)]),
+            [r, (Right, Clockwise, 1)]
+        );
+
+        // L only commutes past R, so R' still reaches back across it to cancel R.
+        let l = (Left, Clockwise, 0);
+        assert_eq!(crate::concat_optimized(&[r], &[l, r_prime]), [l]);
+        for (a, b) in [
+            (&[r][..], &[l, r_prime][..]),
+            (&[u, r], &[l, u, r_prime]),
+            (&[r, l], &[(Left, Half, 0), u_prime, r]),
+        ] {
+            assert_eq!(
+                crate::concat_optimized(a, b),
+                crate::optimize_sequence(&[a, b].concat()),
+                "{a:?} {b:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn optimize_sequence_merges_runs() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let r = (Right, Clockwise, 0);
+        assert_eq!(
+            crate::optimize_sequence(&[r, r, r]),
+            [(Right, CounterClockwise, 0)]
+        );
+        assert_eq!(crate::optimize_sequence(&[r, r, r, r]), []);
+    }
+
+    #[test]
+    fn metric_counts() {
+        use crate::Face::*;
+        use crate::Metric::*;
+        use crate::Movement::*;
+
+        let turn = |movement, depth| crate::Move {
+            face: Right,
+            movement,
+            depth,
+        };
+        for (movement, depth, htm, qtm, stm) in [
+            (Clockwise, 0, 1, 1, 1),
+            (CounterClockwise, 0, 1, 1, 1),
+            (Half, 0, 1, 2, 1),
+            (Clockwise, 1, 2, 2, 1),
+            (CounterClockwise, 1, 2, 2, 1),
+            (Half, 1, 2, 4, 1),
+        ] {
+            let moves = [turn(movement, depth)];
+            assert_eq!(
+                crate::metric_count(&moves, Htm),
+                htm,
+                "{movement:?} {depth}"
+            );
+            assert_eq!(
+                crate::metric_count(&moves, Qtm),
+                qtm,
+                "{movement:?} {depth}"
+            );
+            assert_eq!(
+                crate::metric_count(&moves, Stm),
+                stm,
+                "{movement:?} {depth}"
+            );
+        }
+
+        let moves = [turn(Clockwise, 0), turn(Half, 0), turn(Half, 1)];
+        assert_eq!(crate::metric_count(&moves, Htm), 4);
+        assert_eq!(crate::metric_count(&moves, Qtm), 7);
+        assert_eq!(crate::metric_count(&moves, Stm), 3);
+    }
+
+    #[test]
+    fn simplify_cancels_and_merges() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let turn = |face, movement| crate::Move {
+            face,
+            movement,
+            depth: 0,
+        };
+        let r = turn(Right, Clockwise);
+        let u = turn(Up, Clockwise);
+
+        assert_eq!(crate::simplify(&[r, r, r, r]), []);
+        assert_eq!(crate::simplify(&[r, r]), [turn(Right, Half)]);
+        assert_eq!(crate::simplify(&[r, turn(Right, CounterClockwise)]), []);
+        assert_eq!(crate::simplify(&[r, u, r]), [r, u, r]);
+
+        let moves = [
+            r,
+            u,
+            turn(Up, Half),
+            turn(Up, Clockwise),
+            r,
+            crate::Move { depth: 1, ..r },
+        ];
+        let simplified = crate::simplify(&moves);
+        assert_eq!(
+            simplified,
+            [turn(Right, Half), crate::Move { depth: 1, ..r }]
+        );
+        assert_eq!(crate::simplify(&simplified), simplified);
+    }
+
+    #[test]
+    fn simplify_reorders_opposite_faces() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let turn = |face, movement, depth| crate::Move {
+            face,
+            movement,
+            depth,
+        };
+        let moves = [
+            turn(Right, Clockwise, 0),
+            turn(Left, Clockwise, 0),
+            turn(Right, CounterClockwise, 0),
+            turn(Left, Half, 0),
+            turn(Up, Clockwise, 0),
+        ];
+        // No two neighbours turn the same layer, so merging them alone gets nowhere.
+        assert!(moves
+            .windows(2)
+            .all(|w| (w[0].face, w[0].depth) != (w[1].face, w[1].depth)));
+        let simplified = crate::simplify(&moves);
+        assert_eq!(
+            simplified,
+            [turn(Left, CounterClockwise, 0), turn(Up, Clockwise, 0)]
+        );
+
+        let mut rc = crate::RubiksCube::new(3);
+        rc.apply_moves(&moves);
+        let mut from_simplified = crate::RubiksCube::new(3);
+        from_simplified.apply_moves(&simplified);
+        assert_eq!(rc, from_simplified);
+
+        // Inner layers commute with the outer ones too, but a turn about another axis doesn't.
+        let moves = [
+            turn(Right, Clockwise, 0),
+            turn(Left, Clockwise, 1),
+            turn(Right, CounterClockwise, 0),
+            turn(Up, Clockwise, 0),
+            turn(Left, CounterClockwise, 1),
+        ];
+        assert_eq!(crate::simplify(&moves), [moves[1], moves[3], moves[4]]);
+    }
+
+    #[test]
+    fn corner_commutator_is_a_three_cycle() {
+        use crate::f2l::pieces;
+        use crate::{Move, MoveToken, RubiksCube};
+
+        let parse = |moves: &str| -> Vec<Move> {
+            moves
+                .split_whitespace()
+                .map(|token| match token.parse().unwrap() {
+                    MoveToken::Turn(face, movement, depth) => Move {
+                        face,
+                        movement,
+                        depth,
+                    },
+                    token => panic!("{token} isn't a single turn"),
+                })
+                .collect()
+        };
+        let a = parse("R U R'");
+        let d = parse("D");
+        let moves = crate::commutator(&a, &d);
+        assert_eq!(moves, parse("R U R' D R U' R' D'"));
+
+        let moved = |moves: &[Move]| {
+            let solved = RubiksCube::new(3);
+            let mut rc = solved.clone();
+            rc.apply_moves(moves);
+            pieces()
+                .into_iter()
+                .filter(|piece| {
+                    piece.iter().any(|&(face, row, col)| {
+                        rc.get(face, row, col) != solved.get(face, row, col)
+                    })
+                })
+                .map(|piece| piece.len())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(moved(&moves), [3, 3, 3]);
+
+        let setup = parse("F2");
+        let conjugated = crate::conjugate(&setup, &moves);
+        assert_eq!(conjugated.len(), moves.len() + 2);
+        assert_eq!(conjugated[..1], setup);
+        assert_eq!(moved(&conjugated), [3, 3, 3]);
+        assert_eq!(crate::conjugate(&a, &[]), parse("R U R' R U' R'"));
+    }
+}
@@ -0,0 +1,542 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter};
+use std::hash::Hash;
+use std::time::{Duration, Instant};
+
+use crate::{
+    optimize_sequence, rotate_face, Color, Face, Move, Movement, RubiksCube, ValidationError,
+    COLORS,
+};
+
+const MOVES_2X2: [(Face, Movement); 9] = [
+    (Face::Up, Movement::Clockwise),
+    (Face::Up, Movement::CounterClockwise),
+    (Face::Up, Movement::Half),
+    (Face::Right, Movement::Clockwise),
+    (Face::Right, Movement::CounterClockwise),
+    (Face::Right, Movement::Half),
+    (Face::Front, Movement::Clockwise),
+    (Face::Front, Movement::CounterClockwise),
+    (Face::Front, Movement::Half),
+];
+
+fn opposite_color(color: Color) -> Color {
+    match color {
+        Color::White => Color::Yellow,
+        Color::Yellow => Color::White,
+        Color::Red => Color::Orange,
+        Color::Orange => Color::Red,
+        Color::Blue => Color::Green,
+        Color::Green => Color::Blue,
+    }
+}
+
+/// Packs the 24 stickers of a 2x2x2 into one number, a base 6 digit per sticker.
+fn pack_2x2(rc: &RubiksCube) -> u64 {
+    rc.faces
+        .iter()
+        .flatten()
+        .fold(0, |key, &color| key * 6 + color as u64)
+}
+
+fn unpack_2x2(mut key: u64) -> RubiksCube {
+    let mut rc = RubiksCube::new(2);
+    for face in rc.faces.iter_mut().rev() {
+        for sticker in face.iter_mut().rev() {
+            *sticker = COLORS[(key % 6) as usize];
+            key /= 6;
+        }
+    }
+    rc
+}
+
+fn inverse(m: Move) -> Move {
+    Move {
+        movement: m.movement.inverse(),
+        ..m
+    }
+}
+
+/// Receives progress from a staged solver, so callers can log or display each stage without the
+/// solver depending on a logging crate.
+pub trait SolveObserver {
+    /// Called once a stage is done, with the moves that stage added to the solution.
+    fn on_stage(&mut self, name: &str, moves: &[(Face, Movement)]);
+}
+
+/// Ignores every stage, for callers that only want the solution.
+impl SolveObserver for () {
+    fn on_stage(&mut self, _: &str, _: &[(Face, Movement)]) {}
+}
+
+/// Why [`RubiksCube::solve`] couldn't solve a cube.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolveError {
+    /// No solver handles cubes this big yet.
+    Unsupported { size: usize },
+    /// The cube can't be turned into a solved one; see [`RubiksCube::is_valid`].
+    Invalid(ValidationError),
+    /// The cube looked valid, but no solution turned up. [`RubiksCube::is_valid`] only counts
+    /// colors on a 2x2x2, so an impossible one ends up here.
+    NotFound,
+}
+
+impl Display for SolveError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::Unsupported { size } => {
+                write!(fmt, "can't solve {size}x{size}x{size} cubes yet")
+            }
+            SolveError::Invalid(e) => write!(fmt, "the cube can't be solved: {e}"),
+            SolveError::NotFound => write!(fmt, "no solution found"),
+        }
+    }
+}
+
+impl std::error::Error for SolveError {}
+
+type Frontier = Vec<(RubiksCube, Vec<Move>)>;
+
+/// Expands `frontier` by one move, recording new states in `seen`. Returns the key and path of
+/// the first new state that is already in `other`.
+fn expand(
+    frontier: &mut Frontier,
+    seen: &mut HashMap<Vec<Color>, Vec<Move>>,
+    other: &HashMap<Vec<Color>, Vec<Move>>,
+) -> Option<(Vec<Color>, Vec<Move>)> {
+    let mut next = Vec::new();
+    for (rc, path) in frontier.drain(..) {
+        for (face, movement) in MOVES_2X2 {
+            let mut turned = rc.clone();
+            rotate_face(&mut turned, face, movement, 0);
+            let key = turned.faces.concat();
+            if seen.contains_key(&key) {
+                continue;
+            }
+
+            let mut path = path.clone();
+            path.push(Move {
+                face,
+                movement,
+                depth: 0,
+            });
+            if other.contains_key(&key) {
+                return Some((key, path));
+            }
+            seen.insert(key, path.clone());
+            next.push((turned, path));
+        }
+    }
+    *frontier = next;
+    None
+}
+
+/// Searches breadth-first for the fewest `steps` (each a short sequence of outer turns) that
+/// take `rc` to a state where `done` holds, and returns their moves with neighbouring turns of
+/// the same face merged.
+///
+/// States are told apart only by `key`, so it must capture everything the steps can change that
+/// `done` looks at. Keeping it to the few pieces a stage cares about keeps the search small.
+pub(crate) fn search_steps<K: Hash + Eq + Clone>(
+    rc: &RubiksCube,
+    steps: &[Vec<(Face, Movement)>],
+    key: impl Fn(&RubiksCube) -> K,
+    done: impl Fn(&RubiksCube, &K) -> bool,
+) -> Option<Vec<(Face, Movement)>> {
+    let start = key(rc);
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(rc.clone(), start, Vec::new())]);
+    while let Some((cube, cube_key, path)) = queue.pop_front() {
+        if done(&cube, &cube_key) {
+            let moves: Vec<_> = path
+                .iter()
+                .map(|&(face, movement)| (face, movement, 0))
+                .collect();
+            return Some(
+                optimize_sequence(&moves)
+                    .into_iter()
+                    .map(|(face, movement, _)| (face, movement))
+                    .collect(),
+            );
+        }
+        for step in steps {
+            let mut next = cube.clone();
+            for &(face, movement) in step {
+                rotate_face(&mut next, face, movement, 0);
+            }
+            let next_key = key(&next);
+            if seen.insert(next_key.clone()) {
+                let mut path = path.clone();
+                path.extend_from_slice(step);
+                queue.push_back((next, next_key, path));
+            }
+        }
+    }
+    None
+}
+
+impl RubiksCube {
+    /// Finds a shortest solution (in half turns) for a 2x2x2 cube, or `None` if the cube isn't
+    /// a 2x2x2 or can't be solved.
+    ///
+    /// Only U, R and F are turned, so the down-left-back corner never moves and the cube is
+    /// solved around it. The search runs breadth-first from both the scramble and that solved
+    /// state until the two meet.
+    pub fn solve_2x2(&self) -> Option<Vec<Move>> {
+        if self.size != 2 {
+            return None;
+        }
+
+        let down = self.get(Face::Down, 1, 0);
+        let left = self.get(Face::Left, 1, 0);
+        let back = self.get(Face::Back, 1, 1);
+        let goal = RubiksCube {
+            size: 2,
+            faces: [
+                vec![opposite_color(down); 4],
+                vec![left; 4],
+                vec![opposite_color(back); 4],
+                vec![opposite_color(left); 4],
+                vec![back; 4],
+                vec![down; 4],
+            ],
+            history: None,
+            orientations: None,
+        };
+        if *self == goal {
+            return Some(Vec::new());
+        }
+
+        let mut from_start = HashMap::from([(self.faces.concat(), Vec::new())]);
+        let mut from_goal = HashMap::from([(goal.faces.concat(), Vec::new())]);
+        let mut start_frontier = vec![(self.clone(), Vec::new())];
+        let mut goal_frontier = vec![(goal, Vec::new())];
+
+        while !start_frontier.is_empty() && !goal_frontier.is_empty() {
+            let (mut to_meeting, from_meeting) = if start_frontier.len() <= goal_frontier.len() {
+                match expand(&mut start_frontier, &mut from_start, &from_goal) {
+                    Some((key, path)) => (path, from_goal.remove(&key).unwrap()),
+                    None => continue,
+                }
+            } else {
+                match expand(&mut goal_frontier, &mut from_goal, &from_start) {
+                    Some((key, path)) => (from_start.remove(&key).unwrap(), path),
+                    None => continue,
+                }
+            };
+            to_meeting.extend(from_meeting.into_iter().rev().map(inverse));
+            return Some(to_meeting);
+        }
+
+        None
+    }
+}
+
+impl RubiksCube {
+    /// Solves the cube with whichever solver suits its size: an optimal search on a 2x2x2,
+    /// [Kociemba's algorithm](RubiksCube::solve_kociemba) on a 3x3x3 with the
+    /// [beginner's method](RubiksCube::solve_beginner) to fall back on, and nothing at all on
+    /// a 1x1x1. Bigger cubes aren't supported yet.
+    pub fn solve(&self) -> Result<Vec<Move>, SolveError> {
+        if !(1..=3).contains(&self.size) {
+            return Err(SolveError::Unsupported { size: self.size });
+        }
+        self.is_valid().map_err(SolveError::Invalid)?;
+        if self.is_solved() {
+            return Ok(Vec::new());
+        }
+        let solution = if self.size == 2 {
+            self.solve_2x2()
+        } else {
+            self.solve_kociemba(30).or_else(|| self.solve_beginner())
+        };
+        solution.ok_or(SolveError::NotFound)
+    }
+}
+
+/// The length of an optimal solution for a 2x2x2 cube, in half turns.
+///
+/// Panics if `rc` isn't a solvable 2x2x2.
+pub fn optimal_distance_2x2(rc: &RubiksCube) -> usize {
+    rc.solve_2x2()
+        .expect("optimal_distance_2x2 needs a solvable 2x2x2 cube")
+        .len()
+}
+
+/// Counts the 2x2x2 states reachable from solved in at most `max_depth` half turns, with the
+/// down-left-back corner held in place so that turning the whole cube doesn't count as a new
+/// state.
+pub fn count_states_2x2_within(max_depth: usize) -> u64 {
+    let solved = pack_2x2(&RubiksCube::new(2));
+    let mut seen = HashSet::from([solved]);
+    let mut frontier = vec![solved];
+    for _ in 0..max_depth {
+        let mut next = Vec::new();
+        for key in frontier {
+            let rc = unpack_2x2(key);
+            for (face, movement) in MOVES_2X2 {
+                let mut turned = rc.clone();
+                rotate_face(&mut turned, face, movement, 0);
+                let key = pack_2x2(&turned);
+                if seen.insert(key) {
+                    next.push(key);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        frontier = next;
+    }
+    seen.len() as u64
+}
+
+/// Counts every state of a 2x2x2, up to turning the whole cube: 3,674,160. This walks the
+/// whole state space, so it takes a while and tens of megabytes.
+pub fn count_states_2x2() -> u64 {
+    count_states_2x2_within(usize::MAX)
+}
+
+/// How one solver did over a set of scrambles. See [`compare_solvers`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolverStats {
+    /// How many of the scrambles it found a solution for.
+    pub solved: usize,
+    /// The mean length of its solutions, over the scrambles it solved.
+    pub average_moves: f64,
+    /// The mean time it took per scramble, whether or not it found a solution.
+    pub average_time: Duration,
+}
+
+/// The beginner's method and the two-phase solver side by side on the same scrambles.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SolverComparison {
+    /// How many scrambles both solvers were given.
+    pub scrambles: usize,
+    /// [`RubiksCube::solve_beginner`].
+    pub beginner: SolverStats,
+    /// [`RubiksCube::solve_kociemba`], searching up to 30 moves as [`RubiksCube::solve`] does.
+    pub kociemba: SolverStats,
+}
+
+/// Times `solve` on each cube in `cubes` and totals up its solutions.
+fn solver_stats(
+    cubes: &[RubiksCube],
+    solve: impl Fn(&RubiksCube) -> Option<Vec<Move>>,
+) -> SolverStats {
+    let mut stats = SolverStats::default();
+    let mut moves = 0;
+    let mut time = Duration::ZERO;
+    for rc in cubes {
+        let start = Instant::now();
+        let solution = solve(rc);
+        time += start.elapsed();
+        if let Some(solution) = solution {
+            stats.solved += 1;
+            moves += solution.len();
+        }
+    }
+    stats.average_moves = moves as f64 / stats.solved.max(1) as f64;
+    stats.average_time = time / cubes.len().max(1) as u32;
+    stats
+}
+
+/// Scrambles a solved 3x3x3 with each of `scrambles` and runs both 3x3x3 solvers on it, to show
+/// the trade-off between them: the beginner's method is quick but long-winded, while the
+/// two-phase solver finds far shorter solutions and takes longer to do it.
+pub fn compare_solvers(scrambles: &[Vec<(Face, Movement)>]) -> SolverComparison {
+    let cubes: Vec<RubiksCube> = scrambles
+        .iter()
+        .map(|scramble| {
+            let mut rc = RubiksCube::new(3);
+            for &(face, movement) in scramble {
+                rotate_face(&mut rc, face, movement, 0);
+            }
+            rc
+        })
+        .collect();
+    SolverComparison {
+        scrambles: scrambles.len(),
+        beginner: solver_stats(&cubes, RubiksCube::solve_beginner),
+        kociemba: solver_stats(&cubes, |rc| rc.solve_kociemba(30)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn observers_see_every_move() {
+        use crate::{Face, Movement, SolveObserver};
+
+        struct Recorder(Vec<(String, Vec<(Face, Movement)>)>);
+
+        impl SolveObserver for Recorder {
+            fn on_stage(&mut self, name: &str, moves: &[(Face, Movement)]) {
+                self.0.push((name.to_string(), moves.to_vec()));
+            }
+        }
+
+        let mut rc = crate::RubiksCube::new(3);
+        rc.apply_wca("F2 U' R L D2 B").unwrap();
+        let solution = rc.solve_beginner().unwrap();
+
+        // Doing nothing with the stages mustn't change the solution.
+        ().on_stage("cross", &[(Face::Up, Movement::Clockwise)]);
+        assert_eq!(rc.solve_3x3_explained(&mut ()), Some(solution.clone()));
+
+        let mut recorder = Recorder(Vec::new());
+        assert_eq!(
+            rc.solve_3x3_explained(&mut recorder),
+            Some(solution.clone())
+        );
+        assert_eq!(recorder.0.len(), 7);
+        let observed: Vec<_> = recorder
+            .0
+            .into_iter()
+            .flat_map(|(_, moves)| moves)
+            .collect();
+        let moves: Vec<_> = solution.iter().map(|m| (m.face, m.movement)).collect();
+        assert_eq!(observed, moves);
+    }
+
+    #[test]
+    fn compare_solvers_runs_both() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let scrambles = vec![
+            vec![(Right, Clockwise), (Up, Clockwise), (Front, Half)],
+            vec![
+                (Front, CounterClockwise),
+                (Down, Half),
+                (Left, Clockwise),
+                (Back, Clockwise),
+                (Up, CounterClockwise),
+                (Right, Half),
+            ],
+            vec![
+                (Up, Half),
+                (Left, CounterClockwise),
+                (Back, Half),
+                (Down, Clockwise),
+                (Right, CounterClockwise),
+                (Front, Clockwise),
+                (Up, Clockwise),
+                (Back, CounterClockwise),
+            ],
+        ];
+        let comparison = crate::compare_solvers(&scrambles);
+        assert_eq!(comparison.scrambles, 3);
+        for stats in [comparison.beginner, comparison.kociemba] {
+            assert_eq!(stats.solved, 3);
+            assert!(stats.average_moves > 0.0);
+            assert!(stats.average_time > std::time::Duration::ZERO);
+        }
+        assert!(comparison.kociemba.average_moves < comparison.beginner.average_moves);
+
+        assert_eq!(crate::compare_solvers(&[]).beginner.solved, 0);
+    }
+
+    #[test]
+    fn solve_2x2_scramble() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(2);
+        let scramble = [
+            (Right, Clockwise),
+            (Up, CounterClockwise),
+            (Front, Half),
+            (Down, Clockwise),
+            (Left, CounterClockwise),
+            (Up, Half),
+            (Back, Clockwise),
+            (Right, Half),
+        ];
+        for (face, movement) in scramble {
+            crate::rotate_face(&mut rc, face, movement, 0);
+        }
+        assert!(!rc.is_solved());
+
+        let solution = rc.solve_2x2().unwrap();
+        assert!(solution.len() <= 11);
+        for m in solution {
+            crate::rotate_face(&mut rc, m.face, m.movement, m.depth);
+        }
+        assert!(rc.is_solved());
+    }
+
+    #[test]
+    fn solve_2x2_is_optimal_and_sized() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(2);
+        crate::rotate_face(&mut rc, Right, Clockwise, 0);
+        crate::rotate_face(&mut rc, Up, Clockwise, 0);
+        assert_eq!(rc.solve_2x2().unwrap().len(), 2);
+        assert_eq!(crate::RubiksCube::new(2).solve_2x2(), Some(Vec::new()));
+        assert_eq!(crate::RubiksCube::new(3).solve_2x2(), None);
+    }
+
+    #[test]
+    fn optimal_distance_2x2_counts_moves() {
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(2);
+        assert_eq!(crate::optimal_distance_2x2(&rc), 0);
+        crate::rotate_face(&mut rc, Left, CounterClockwise, 0);
+        assert_eq!(crate::optimal_distance_2x2(&rc), 1);
+        crate::rotate_face(&mut rc, Down, Half, 0);
+        assert_eq!(crate::optimal_distance_2x2(&rc), 2);
+    }
+
+    #[test]
+    fn count_2x2_states_near_solved() {
+        assert_eq!(crate::count_states_2x2_within(0), 1);
+        assert_eq!(crate::count_states_2x2_within(1), 1 + 9);
+        assert_eq!(crate::count_states_2x2_within(3), 1 + 9 + 54 + 321);
+    }
+
+    #[test]
+    #[ignore = "walks all 3.7 million states"]
+    fn count_all_2x2_states() {
+        assert_eq!(crate::count_states_2x2(), 3_674_160);
+    }
+
+    #[test]
+    fn solve_picks_a_solver_by_size() {
+        use crate::{RubiksCube, SolveError, ValidationError};
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(565);
+        for size in [1, 2, 3] {
+            let mut rc = RubiksCube::new(size);
+            assert_eq!(rc.solve(), Ok(Vec::new()));
+            rc.apply_moves(&crate::scramble(size, 25, &mut rng));
+            let solution = rc.solve().unwrap();
+            rc.apply_moves(&solution);
+            assert!(rc.is_solved(), "size {size}");
+        }
+
+        assert_eq!(
+            RubiksCube::new(4).solve(),
+            Err(SolveError::Unsupported { size: 4 })
+        );
+        let mut twisted = RubiksCube::new(3);
+        let corner = [
+            (crate::Face::Up, 2, 2),
+            (crate::Face::Front, 0, 2),
+            (crate::Face::Right, 0, 0),
+        ];
+        let colors = corner.map(|(face, row, col)| twisted.get(face, row, col));
+        for (i, (face, row, col)) in corner.into_iter().enumerate() {
+            twisted.set(face, row, col, colors[(i + 1) % 3]);
+        }
+        assert_eq!(
+            twisted.solve(),
+            Err(SolveError::Invalid(ValidationError::CornerTwist))
+        );
+    }
+}
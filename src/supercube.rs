@@ -0,0 +1,190 @@
+//! Supercubes, whose stickers each show which way they point, so a center turned in place
+//! leaves the cube unsolved.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    get_sides, position_based_off_corner_and_move_count, turn_layer, Corner, Face, Movement,
+    RubiksCube, Side, Stickers,
+};
+
+/// The orientation of every sticker of a cube, turned along with its colors.
+struct Orientations<'a> {
+    size: usize,
+    faces: &'a mut [Vec<u8>; 6],
+}
+
+impl Stickers for Orientations<'_> {
+    type Sticker = u8;
+
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn get(&self, face: Face, row: usize, col: usize) -> u8 {
+        self.faces[face as usize][row * self.size + col]
+    }
+
+    fn set(&mut self, face: Face, row: usize, col: usize, orientation: u8) {
+        self.faces[face as usize][row * self.size + col] = orientation;
+    }
+}
+
+/// Which way the strip of stickers that starts at `corner` runs across its face, in quarter
+/// turns clockwise from pointing up the face.
+fn strip_direction(corner: Corner) -> u8 {
+    match corner {
+        Corner::BottomRight => 0,
+        Corner::BottomLeft => 1,
+        Corner::TopLeft => 2,
+        Corner::TopRight => 3,
+    }
+}
+
+/// Moves `orientations` with a turn, the same way [`turn_layer`] moves colors, then turns each
+/// moved sticker's orientation with it: the stickers of a turned face by the turn itself, and
+/// each strip by how its direction across the face it left differs from the face it's on now.
+pub(crate) fn turn_orientations(
+    orientations: &mut [Vec<u8>; 6],
+    size: usize,
+    face: Face,
+    movement: Movement,
+    depth: usize,
+) {
+    turn_layer(
+        &mut Orientations {
+            size,
+            faces: orientations,
+        },
+        face,
+        movement,
+        depth,
+    );
+
+    let mut turn_grid = |face: Face, movement: Movement| {
+        for orientation in &mut orientations[face as usize] {
+            *orientation = (*orientation + movement.quarter_turns()) % 4;
+        }
+    };
+    if depth == 0 {
+        turn_grid(face, movement);
+    }
+    if depth == size - 1 {
+        turn_grid(face.opposite(), movement.inverse());
+    }
+
+    let sides = get_sides(face);
+    for (side, &Side(to_face, to_corner)) in sides.iter().enumerate() {
+        let Side(_, from_corner) = sides[(side + 4 - movement.quarter_turns() as usize) % 4];
+        let delta = (4 + strip_direction(to_corner) - strip_direction(from_corner)) % 4;
+        for i in 0..size {
+            let (row, col) = position_based_off_corner_and_move_count(to_corner, i, size, depth);
+            let orientation = &mut orientations[to_face as usize][row * size + col];
+            *orientation = (*orientation + delta) % 4;
+        }
+    }
+}
+
+/// Whether the sticker at `row`, `col` is a center sticker, away from the edges of its face.
+pub(crate) fn is_center(size: usize, row: usize, col: usize) -> bool {
+    let inner = 1..size.saturating_sub(1);
+    inner.contains(&row) && inner.contains(&col)
+}
+
+impl RubiksCube {
+    /// A solved supercube of `size`: an ordinary cube, except that every sticker keeps track of
+    /// which way it points as the cube is turned. [`RubiksCube::is_solved`] then also needs
+    /// every center upright, and the printed net marks each center with an arrow.
+    ///
+    /// Only turns move the marks; recoloring stickers with [`RubiksCube::set`] or reflecting
+    /// the cube with [`RubiksCube::mirror`] leaves them as they were.
+    pub fn new_supercube(size: usize) -> RubiksCube {
+        let mut rc = RubiksCube::new(size);
+        rc.orientations = Some(core::array::from_fn(|_| vec![0; size * size]));
+        rc
+    }
+
+    /// Whether the cube was made by [`RubiksCube::new_supercube`].
+    pub fn is_supercube(&self) -> bool {
+        self.orientations.is_some()
+    }
+
+    /// Which way the sticker at `row`, `col` of `face` points on a supercube, in quarter turns
+    /// clockwise from upright as the face is laid out in the net. `None` for an ordinary cube.
+    pub fn orientation(&self, face: Face, row: usize, col: usize) -> Option<u8> {
+        self.check_sticker(face, row, col);
+        let orientations = self.orientations.as_ref()?;
+        Some(orientations[face as usize][row * self.size + col])
+    }
+
+    /// Whether every sticker points the way it would on a solved supercube held in some
+    /// orientation. Always true for an ordinary cube.
+    pub(crate) fn orientations_solved(&self) -> bool {
+        self.orientations.is_none()
+            || RubiksCube::new_supercube(self.size).equals_ignoring_orientation(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn rotations_turn_the_centers() {
+        use crate::Axis;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let centers = |rc: &crate::RubiksCube| {
+            crate::Face::ALL.map(|face| rc.orientation(face, 1, 1).unwrap())
+        };
+
+        let mut rc = crate::RubiksCube::new_supercube(3);
+        assert_eq!(centers(&rc), [0; 6]);
+        // x takes front to up, up to back, back to down and down to front.
+        rc.rotate_cube(Axis::X, Clockwise);
+        assert_eq!(centers(&rc), [0, 3, 0, 1, 2, 2]);
+        assert!(rc.is_solved());
+
+        let mut rc = crate::RubiksCube::new_supercube(3);
+        rc.rotate_cube(Axis::Y, Clockwise);
+        assert_eq!(centers(&rc), [1, 0, 0, 0, 0, 3]);
+        assert!(rc.is_solved());
+        assert!(rc.to_string().contains('→'));
+
+        assert_eq!(crate::RubiksCube::new(3).orientation(Up, 1, 1), None);
+    }
+
+    #[test]
+    fn twisted_center_is_unsolved() {
+        use crate::RubiksCube;
+
+        let alg = "U R L U2 R' L' U R L U2 R' L'";
+        let mut plain = RubiksCube::new(3);
+        plain.apply_wca(alg).unwrap();
+        assert!(plain.is_solved());
+
+        let mut rc = RubiksCube::new_supercube(3);
+        rc.apply_wca(alg).unwrap();
+        assert_eq!(rc.orientation(crate::Face::Up, 1, 1), Some(2));
+        assert!(!rc.is_solved());
+        rc.apply_wca(alg).unwrap();
+        assert!(rc.is_solved());
+        assert_eq!(rc, RubiksCube::new_supercube(3));
+    }
+
+    #[test]
+    fn scrambles_undo_on_big_supercubes() {
+        use rand::SeedableRng;
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(571);
+        for size in [2, 4, 5] {
+            let moves = crate::scramble(size, 40, &mut rng);
+            let mut rc = crate::RubiksCube::new_supercube(size);
+            rc.apply_moves(&moves);
+            assert!(!rc.is_solved());
+            while rc.undo().is_some() {}
+            assert_eq!(rc, crate::RubiksCube::new_supercube(size), "size {size}");
+            assert!(rc.is_solved());
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use alloc::format;
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{ColorScheme, Face, RubiksCube};
+
+/// Width and height of one sticker, in SVG user units.
+const STICKER_SIZE: usize = 20;
+
+/// Where each face sits in the net, in face widths from the top left.
+pub(crate) const NET_POSITIONS: [(Face, usize, usize); 6] = [
+    (Face::Up, 1, 0),
+    (Face::Left, 0, 1),
+    (Face::Front, 1, 1),
+    (Face::Right, 2, 1),
+    (Face::Back, 3, 1),
+    (Face::Down, 1, 2),
+];
+
+impl RubiksCube {
+    /// Draws the cube as a standalone SVG document, laid out in the same net as its
+    /// [`Display`](core::fmt::Display) output, with one `<rect>` per sticker.
+    pub fn to_svg(&self) -> String {
+        self.to_svg_with_scheme(&ColorScheme::default())
+    }
+
+    /// Like [`RubiksCube::to_svg`], with stickers filled in `scheme`'s colors.
+    pub fn to_svg_with_scheme(&self, scheme: &ColorScheme) -> String {
+        let face_size = self.size * STICKER_SIZE;
+        let (width, height) = (4 * face_size, 3 * face_size);
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\n"
+        );
+        for (face, face_x, face_y) in NET_POSITIONS {
+            for (i, color) in self[face].iter().enumerate() {
+                let x = face_x * face_size + i % self.size * STICKER_SIZE;
+                let y = face_y * face_size + i / self.size * STICKER_SIZE;
+                let (r, g, b) = scheme.rgb(*color);
+                writeln!(
+                    svg,
+                    "<rect x=\"{x}\" y=\"{y}\" width=\"{STICKER_SIZE}\" height=\"{STICKER_SIZE}\" \
+                     fill=\"rgb({r},{g},{b})\" stroke=\"black\"/>"
+                )
+                .expect("writing to a String can't fail");
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn svg_has_a_rect_per_sticker() {
+        let mut rc = crate::RubiksCube::new(3);
+        crate::rotate_face(&mut rc, crate::Face::Right, crate::Movement::Clockwise, 0);
+        let svg = rc.to_svg();
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.contains("width=\"240\" height=\"180\""));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<rect ").count(), 6 * 9);
+
+        // The top right sticker of the front face was brought down from the up face.
+        assert!(svg.contains(
+            "<rect x=\"100\" y=\"60\" width=\"20\" height=\"20\" fill=\"rgb(238,238,238)\""
+        ));
+        assert!(svg
+            .contains("<rect x=\"60\" y=\"0\" width=\"20\" height=\"20\" fill=\"rgb(255,255,0)\""));
+    }
+}
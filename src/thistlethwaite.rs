@@ -0,0 +1,138 @@
+//! Where a 3x3x3 sits in Thistlethwaite's chain of nested subgroups.
+//!
+//! G0 = <L, R, F, B, U, D> ⊃ G1 = <L, R, F, B, U2, D2> ⊃ G2 = <L, R, F2, B2, U2, D2>
+//! ⊃ G3 = <L2, R2, F2, B2, U2, D2> ⊃ G4 = {solved}.
+
+use std::collections::HashSet;
+
+use crate::f2l::{pieces, Sticker};
+use crate::kociemba::{basic_moves, is_odd, CubieCube};
+use crate::{Axis, Face, RubiksCube};
+
+/// The corner permutations reachable with half turns alone. There are 96 of them.
+fn half_turn_corner_perms() -> HashSet<[u8; 8]> {
+    let half_turns: Vec<_> = basic_moves().into_iter().skip(1).step_by(3).collect();
+    let mut seen = HashSet::from([CubieCube::SOLVED.cp]);
+    let mut frontier = vec![CubieCube::SOLVED];
+    while let Some(cube) = frontier.pop() {
+        for turn in &half_turns {
+            let next = cube.multiply(turn);
+            if seen.insert(next.cp) {
+                frontier.push(next);
+            }
+        }
+    }
+    seen
+}
+
+impl RubiksCube {
+    /// How deep into Thistlethwaite's subgroup chain G0 ⊃ G1 ⊃ G2 ⊃ G3 ⊃ {solved} the cube
+    /// lies: the largest `n` such that it's in Gn, so 4 only when solved. Colors are read
+    /// relative to the centers, as everywhere else on the 3x3x3.
+    ///
+    /// - G1: every edge is good for U and D quarter turns. An edge's key sticker is its front
+    ///   or back color, or its up or down color if it has neither, and it's good if that
+    ///   sticker is on the front or back face, or on the up or down face if the edge isn't on
+    ///   the front or back.
+    /// - G2: also, every corner has its left or right color on the left or right face, and the
+    ///   four edges without a left or right color are in the layer between L and R.
+    /// - G3: also, every face shows only its own color and the opposite one, the corners are
+    ///   in one of the 96 arrangements half turns can make, and the edges are evenly permuted.
+    ///
+    /// Cubes that aren't [valid](RubiksCube::is_valid) report 0. Panics unless the cube is a
+    /// 3x3x3.
+    pub fn thistlethwaite_phase(&self) -> u8 {
+        assert_eq!(self.size, 3, "Thistlethwaite's subgroups are of the 3x3x3");
+        if self.is_valid().is_err() {
+            return 0;
+        }
+
+        let center = |face: Face| self.get(face, 1, 1);
+        let color_axis = |&(face, row, col): &Sticker| {
+            let color = self.get(face, row, col);
+            Face::ALL
+                .into_iter()
+                .find(|&face| center(face) == color)
+                .map_or(Axis::Y, Face::axis)
+        };
+        let face_axis = |&(face, _, _): &Sticker| face.axis();
+        let pieces = pieces();
+        let edges = pieces.iter().filter(|piece| piece.len() == 2);
+        let corners = pieces.iter().filter(|piece| piece.len() == 3);
+
+        let first_on = |piece: &[Sticker], axis_of: &dyn Fn(&Sticker) -> Axis| {
+            [Axis::Z, Axis::Y]
+                .into_iter()
+                .find_map(|want| piece.iter().find(|sticker| axis_of(sticker) == want))
+                .copied()
+        };
+        let edges_good = edges
+            .clone()
+            .all(|edge| first_on(edge, &color_axis) == first_on(edge, &face_axis));
+        if !edges_good {
+            return 0;
+        }
+
+        let corners_oriented = corners.clone().all(|corner| {
+            corner
+                .iter()
+                .filter(|sticker| color_axis(sticker) == Axis::X)
+                .all(|sticker| face_axis(sticker) == Axis::X)
+        });
+        let middle_edges_home = edges.clone().all(|edge| {
+            edge.iter().any(|sticker| color_axis(sticker) == Axis::X)
+                || edge.iter().all(|sticker| face_axis(sticker) != Axis::X)
+        });
+        if !corners_oriented || !middle_edges_home {
+            return 1;
+        }
+
+        let faces_two_colored = pieces
+            .iter()
+            .flatten()
+            .all(|sticker| color_axis(sticker) == face_axis(sticker));
+        let cube = CubieCube::from_facelets(&self.to_facelet_string())
+            .expect("a valid 3x3x3 is made of real pieces");
+        if !faces_two_colored || is_odd(&cube.ep) || !half_turn_corner_perms().contains(&cube.cp) {
+            return 2;
+        }
+
+        if self.is_solved() {
+            4
+        } else {
+            3
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn phases_follow_the_generators() {
+        use crate::RubiksCube;
+
+        let phase = |moves: &str| {
+            let mut rc = RubiksCube::new(3);
+            rc.apply_wca(moves).unwrap();
+            rc.thistlethwaite_phase()
+        };
+        assert_eq!(phase(""), 4);
+        assert_eq!(phase("R2 U2 F2 L2 D2 B2"), 3);
+        assert_eq!(phase("R U2 F2 L' D2 B2"), 2);
+        assert_eq!(phase("F R B U2 L D2"), 1);
+        assert_eq!(phase("R U F"), 0);
+        assert_eq!(phase("U"), 0);
+        assert_eq!(phase("F"), 1);
+        assert_eq!(phase("R"), 2);
+
+        // Every face two-colored, but the corners in an arrangement half turns can't make.
+        assert_eq!(phase("R2 U L2 B2 D' F2 U2 L2 U' B2 R2 D'"), 2);
+    }
+
+    #[test]
+    fn rotated_cube_is_solved() {
+        let mut rc = crate::RubiksCube::new(3);
+        rc.apply_wca("x y").unwrap();
+        assert_eq!(rc.thistlethwaite_phase(), 4);
+    }
+}
@@ -0,0 +1,100 @@
+//! A cube to play with in the terminal. Letter keys turn faces, digits pick the layer to turn
+//! and Escape quits.
+
+use std::io::Write;
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::{cursor, execute, terminal};
+
+use crate::{face_from_letter, rotate_face, Face, Movement, RubiksCube};
+
+/// What a key press asks for.
+#[derive(Debug, PartialEq, Eq)]
+enum Action {
+    Turn(Face, Movement),
+    Depth(usize),
+    Quit,
+    Ignore,
+}
+
+/// Lowercase face letters turn clockwise and uppercase ones counter-clockwise. Digits choose the
+/// layer, counted in from the turned face.
+fn action(key: KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc => Action::Quit,
+        KeyCode::Char(c) if c.is_ascii_digit() => Action::Depth(c as usize - '0' as usize),
+        KeyCode::Char(c) => match face_from_letter(c.to_ascii_uppercase()) {
+            Some(face) if c.is_ascii_lowercase() => Action::Turn(face, Movement::Clockwise),
+            Some(face) => Action::Turn(face, Movement::CounterClockwise),
+            None => Action::Ignore,
+        },
+        _ => Action::Ignore,
+    }
+}
+
+fn draw(out: &mut impl Write, rc: &RubiksCube, depth: usize) -> std::io::Result<()> {
+    execute!(
+        out,
+        terminal::Clear(terminal::ClearType::All),
+        cursor::MoveTo(0, 0)
+    )?;
+    // Raw mode doesn't move back to the start of the line on a newline.
+    write!(out, "{}", rc.to_string().replace('\n', "\r\n"))?;
+    write!(
+        out,
+        "\r\nlayer {depth}  |  u l f r b d: turn, shift: turn back, 0-9: layer, esc: quit\r\n"
+    )?;
+    out.flush()
+}
+
+/// Draws `rc` and turns it as keys are pressed until Escape, then puts the terminal back.
+pub fn run(rc: &mut RubiksCube) -> std::io::Result<()> {
+    let mut out = std::io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(out, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    let result = (|| {
+        let mut depth = 0;
+        loop {
+            draw(&mut out, rc, depth)?;
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            match action(key) {
+                Action::Turn(face, movement) => rotate_face(rc, face, movement, depth),
+                Action::Depth(d) if d < rc.size => depth = d,
+                Action::Quit => return Ok(()),
+                _ => {}
+            }
+        }
+    })();
+
+    execute!(out, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn keys_map_to_actions() {
+        use crate::tui::{action, Action};
+        use crate::Face::*;
+        use crate::Movement::*;
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let press = |code| action(KeyEvent::new(code, KeyModifiers::NONE));
+        assert_eq!(press(KeyCode::Char('r')), Action::Turn(Right, Clockwise));
+        assert_eq!(
+            press(KeyCode::Char('U')),
+            Action::Turn(Up, CounterClockwise)
+        );
+        assert_eq!(press(KeyCode::Char('2')), Action::Depth(2));
+        assert_eq!(press(KeyCode::Esc), Action::Quit);
+        assert_eq!(press(KeyCode::Char('q')), Action::Ignore);
+        assert_eq!(press(KeyCode::Enter), Action::Ignore);
+    }
+}
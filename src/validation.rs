@@ -0,0 +1,666 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
+
+use crate::{solved_color, sticker_position, Color, Face, RubiksCube};
+
+/// Counts the pieces whose colors don't belong to any piece of a solved cube with the same
+/// centers, counting a piece twice over if it appears more often than it should.
+fn misplaced_pieces(rc: &RubiksCube) -> usize {
+    let center = |face: Face| rc.get(face, rc.size / 2, rc.size / 2);
+
+    let mut pieces: HashMap<_, (Vec<Color>, Vec<Color>)> = HashMap::new();
+    for face in Face::ALL {
+        for row in 0..rc.size {
+            for col in 0..rc.size {
+                let piece = pieces
+                    .entry(sticker_position(rc.size, face, row, col))
+                    .or_default();
+                piece.0.push(rc.get(face, row, col));
+                piece.1.push(center(face));
+            }
+        }
+    }
+
+    let mut surplus: HashMap<Vec<Color>, isize> = HashMap::new();
+    for (mut actual, mut expected) in pieces.into_values() {
+        actual.sort_by_key(|&c| c as u8);
+        expected.sort_by_key(|&c| c as u8);
+        *surplus.entry(actual).or_default() += 1;
+        *surplus.entry(expected).or_default() -= 1;
+    }
+    surplus.values().filter(|&&n| n > 0).sum::<isize>() as usize
+}
+
+/// For a scanned 3x3x3 with one color showing up once too often and another once too rarely,
+/// suggests the sticker to recolor and its new color.
+///
+/// Every sticker of the surplus color is tried as the mis-scanned one, and the fix that leaves
+/// the fewest pieces with impossible colors wins. Returns `None` for other sizes or when the
+/// color counts aren't off by exactly one in this way.
+pub fn suggest_scan_fix(rc: &RubiksCube) -> Option<((Face, usize, usize), Color)> {
+    if rc.size != 3 {
+        return None;
+    }
+
+    let mut counts: HashMap<Color, usize> = HashMap::new();
+    for face in &rc.faces {
+        for &color in face {
+            *counts.entry(color).or_default() += 1;
+        }
+    }
+    let mut surplus = None;
+    let mut missing = None;
+    for color in [
+        Color::White,
+        Color::Yellow,
+        Color::Red,
+        Color::Orange,
+        Color::Blue,
+        Color::Green,
+    ] {
+        match counts.get(&color).copied().unwrap_or(0) {
+            9 => {}
+            10 if surplus.is_none() => surplus = Some(color),
+            8 if missing.is_none() => missing = Some(color),
+            _ => return None,
+        }
+    }
+    let (surplus, missing) = (surplus?, missing?);
+
+    let mut candidate = rc.clone();
+    let mut best: Option<((Face, usize, usize), usize)> = None;
+    for face in Face::ALL {
+        for row in 0..3 {
+            for col in 0..3 {
+                if (row, col) == (1, 1) || rc.get(face, row, col) != surplus {
+                    continue;
+                }
+                candidate.set(face, row, col, missing);
+                let errors = misplaced_pieces(&candidate);
+                candidate.set(face, row, col, surplus);
+                if best.is_none_or(|(_, fewest)| errors < fewest) {
+                    best = Some(((face, row, col), errors));
+                }
+            }
+        }
+    }
+    best.map(|(sticker, _)| (sticker, missing))
+}
+
+type Position = (usize, usize, usize);
+type Sticker = (Face, Color);
+type StickersByPosition = BTreeMap<Position, Vec<(Sticker, (usize, usize))>>;
+
+fn normal(face: Face) -> [i32; 3] {
+    match face {
+        Face::Up => [0, 1, 0],
+        Face::Down => [0, -1, 0],
+        Face::Front => [0, 0, 1],
+        Face::Back => [0, 0, -1],
+        Face::Left => [-1, 0, 0],
+        Face::Right => [1, 0, 0],
+    }
+}
+
+fn cross(a: [i32; 3], b: [i32; 3]) -> [i32; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [i32; 3], b: [i32; 3]) -> i32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+/// The colors of a corner, read counter-clockwise around it from outside and starting from
+/// the lowest color. Turns move corners around without changing this.
+fn corner_colors(stickers: &[Sticker; 3]) -> [Color; 3] {
+    let mut colors = stickers.map(|(_, color)| color);
+    let handedness = dot(
+        cross(normal(stickers[0].0), normal(stickers[1].0)),
+        normal(stickers[2].0),
+    );
+    if handedness < 0 {
+        colors.swap(1, 2);
+    }
+    let lowest = (0..3).min_by_key(|&i| colors[i] as u8).unwrap();
+    colors.rotate_left(lowest);
+    colors
+}
+
+/// How far a corner is twisted from having its up/down colored sticker on the up or down face.
+/// The twists of a reachable state add up to a multiple of three.
+fn corner_twist(stickers: &[Sticker; 3]) -> usize {
+    let mut stickers = *stickers;
+    let handedness = dot(
+        cross(normal(stickers[0].0), normal(stickers[1].0)),
+        normal(stickers[2].0),
+    );
+    if handedness < 0 {
+        stickers.swap(1, 2);
+    }
+    let up_down_colors = [solved_color(Face::Up), solved_color(Face::Down)];
+    let face = stickers
+        .iter()
+        .position(|(face, _)| matches!(face, Face::Up | Face::Down));
+    let color = stickers
+        .iter()
+        .position(|(_, color)| up_down_colors.contains(color));
+    match (face, color) {
+        (Some(face), Some(color)) => (color + 3 - face) % 3,
+        _ => 0,
+    }
+}
+
+/// Whether a middle edge is flipped, by the usual up/down then front/back rule. A reachable
+/// state has an even number of them.
+fn edge_flipped(stickers: &[Sticker; 2]) -> bool {
+    let primary = stickers
+        .iter()
+        .position(|(face, _)| matches!(face, Face::Up | Face::Down))
+        .or_else(|| {
+            stickers
+                .iter()
+                .position(|(face, _)| matches!(face, Face::Front | Face::Back))
+        })
+        .unwrap_or(0);
+    let (on_primary, other) = (stickers[primary].1, stickers[1 - primary].1);
+    let up_down = [solved_color(Face::Up), solved_color(Face::Down)];
+    let front_back = [solved_color(Face::Front), solved_color(Face::Back)];
+    let good = up_down.contains(&on_primary)
+        || (front_back.contains(&on_primary) && !up_down.contains(&other));
+    !good
+}
+
+/// The colors of a wing (an edge piece off the middle of its edge), in an order fixed by which
+/// end of the edge it's nearer. Wings can't be flipped in place, so turns keep this order.
+fn wing_colors(stickers: &[Sticker; 2], position: Position, size: usize) -> [Color; 2] {
+    let position = [position.0, position.1, position.2];
+    let free_axis = (0..3)
+        .find(|&axis| position[axis] != 0 && position[axis] != size - 1)
+        .expect("wings have one coordinate off the cube's surface");
+    let mut towards_end = [0; 3];
+    towards_end[free_axis] = if 2 * position[free_axis] > size - 1 {
+        1
+    } else {
+        -1
+    };
+    let [a, b] = *stickers;
+    if dot(cross(normal(a.0), normal(b.0)), towards_end) > 0 {
+        [a.1, b.1]
+    } else {
+        [b.1, a.1]
+    }
+}
+
+/// The stickers of a cube grouped by the kind of piece they're on. Corners, middle edges and
+/// fixed centers are kept by position since they're all distinct; wings and centers are only
+/// counted, per orbit of positions they can move between.
+#[derive(Default)]
+struct Pieces {
+    corners: BTreeMap<Position, [Sticker; 3]>,
+    middle_edges: BTreeMap<Position, [Sticker; 2]>,
+    fixed_centers: BTreeMap<Position, Sticker>,
+    wings: HashMap<(usize, [Color; 2]), usize>,
+    centers: HashMap<((usize, usize), Color), usize>,
+}
+
+fn pieces(rc: &RubiksCube) -> Pieces {
+    let n = rc.size;
+    let s = n - 1;
+    let mut by_position = StickersByPosition::new();
+    for face in Face::ALL {
+        for row in 0..n {
+            for col in 0..n {
+                by_position
+                    .entry(sticker_position(n, face, row, col))
+                    .or_default()
+                    .push(((face, rc.get(face, row, col)), (row, col)));
+            }
+        }
+    }
+
+    let mut pieces = Pieces::default();
+    for (position, stickers) in by_position {
+        match stickers[..] {
+            [(a, _), (b, _), (c, _)] => {
+                pieces.corners.insert(position, [a, b, c]);
+            }
+            [(a, _), (b, _)] => {
+                let [x, y, z] = [position.0, position.1, position.2];
+                let t = [x, y, z].into_iter().find(|&t| t != 0 && t != s).unwrap();
+                if 2 * t == s {
+                    pieces.middle_edges.insert(position, [a, b]);
+                } else {
+                    let orbit = t.min(s - t);
+                    let colors = wing_colors(&[a, b], position, n);
+                    *pieces.wings.entry((orbit, colors)).or_default() += 1;
+                }
+            }
+            [(sticker, (row, col))] => {
+                if 2 * row == s && 2 * col == s {
+                    pieces.fixed_centers.insert(position, sticker);
+                } else {
+                    let orbit = [
+                        (row, col),
+                        (col, s - row),
+                        (s - row, s - col),
+                        (s - col, row),
+                    ]
+                    .into_iter()
+                    .min()
+                    .unwrap();
+                    *pieces.centers.entry((orbit, sticker.1)).or_default() += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    pieces
+}
+
+/// Whether the pieces at each position, named by their sorted colors, are a permutation of the
+/// solved ones, and if so that permutation's parity.
+fn permutation_parity<const N: usize>(
+    pieces: &BTreeMap<Position, [Sticker; N]>,
+    solved: &BTreeMap<Position, [Sticker; N]>,
+) -> Option<bool> {
+    let name = |stickers: &[Sticker; N]| {
+        let mut colors = stickers.map(|(_, color)| color as u8);
+        colors.sort();
+        colors
+    };
+    let home: HashMap<_, _> = solved
+        .values()
+        .enumerate()
+        .map(|(i, stickers)| (name(stickers), i))
+        .collect();
+    let permutation = pieces
+        .values()
+        .map(|stickers| home.get(&name(stickers)).copied())
+        .collect::<Option<Vec<_>>>()?;
+    cycle_parity(&permutation)
+}
+
+/// The parity of `permutation` (odd is `true`), or `None` if it isn't one.
+fn cycle_parity(permutation: &[usize]) -> Option<bool> {
+    let mut seen = vec![false; permutation.len()];
+    let mut odd = false;
+    for start in 0..permutation.len() {
+        if seen[start] {
+            continue;
+        }
+        let mut i = start;
+        let mut length = 0;
+        while !seen[i] {
+            seen[i] = true;
+            i = permutation[i];
+            length += 1;
+        }
+        if i != start {
+            return None;
+        }
+        odd ^= length % 2 == 0;
+    }
+    Some(odd)
+}
+
+fn counts<T: std::hash::Hash + Eq>(items: impl Iterator<Item = T>) -> HashMap<T, usize> {
+    let mut counts = HashMap::new();
+    for item in items {
+        *counts.entry(item).or_default() += 1;
+    }
+    counts
+}
+
+/// Whether the middle centers of an odd cube are arranged as on a solved cube turned as a
+/// whole: opposite colors on opposite faces, going around the same way.
+fn centers_form_a_frame(rc: &RubiksCube) -> bool {
+    let center = |face: Face| rc.get(face, rc.size / 2, rc.size / 2);
+    let frame = [Face::Up, Face::Front, Face::Right].map(|face| (face, center(face)));
+    let frame_turns_right_way = pieces(&RubiksCube::new(2))
+        .corners
+        .values()
+        .any(|corner| corner_colors(corner) == corner_colors(&frame));
+    let opposites_match = Face::ALL.iter().all(|&face| {
+        let opposite = face.opposite();
+        Face::ALL.iter().any(|&home| {
+            solved_color(home) == center(face) && solved_color(home.opposite()) == center(opposite)
+        })
+    });
+    frame_turns_right_way && opposites_match
+}
+
+/// Why [`RubiksCube::is_valid`] rejected a cube.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A color is on the wrong number of stickers.
+    ColorCount {
+        color: Color,
+        count: usize,
+        expected: usize,
+    },
+    /// Some stickers don't make up pieces a real cube has, such as an edge with two opposite
+    /// colors, or the centers are in an impossible arrangement.
+    ImpossiblePieces,
+    /// The corners are twisted by less than a whole turn in total.
+    CornerTwist,
+    /// An odd number of edges are flipped.
+    EdgeFlip,
+    /// The corners and edges are permuted with different parity, as if two pieces were swapped.
+    PermutationParity,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::ColorCount {
+                color,
+                count,
+                expected,
+            } => write!(
+                fmt,
+                "{color:?} is on {count} stickers instead of {expected}"
+            ),
+            ValidationError::ImpossiblePieces => {
+                write!(fmt, "some pieces don't exist on a real cube")
+            }
+            ValidationError::CornerTwist => write!(fmt, "a corner is twisted"),
+            ValidationError::EdgeFlip => write!(fmt, "an edge is flipped"),
+            ValidationError::PermutationParity => write!(fmt, "two pieces are swapped"),
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+impl RubiksCube {
+    /// Checks that the cube describes a state a real cube can be turned into, for example
+    /// before handing a scanned or parsed cube to a solver.
+    ///
+    /// Every size must have each color on exactly `size * size` stickers. A 3x3x3 must also
+    /// be made of real pieces, with the corner twists adding up to a whole turn, an even number
+    /// of flipped edges, and corners and edges permuted with the same parity. See
+    /// [`RubiksCube::is_reachable`] for a check on every size that doesn't say what's wrong.
+    pub fn is_valid(&self) -> Result<(), ValidationError> {
+        let color_counts = counts(self.faces.iter().flatten().copied());
+        for color in [
+            Color::White,
+            Color::Yellow,
+            Color::Red,
+            Color::Orange,
+            Color::Blue,
+            Color::Green,
+        ] {
+            let count = color_counts.get(&color).copied().unwrap_or(0);
+            let expected = self.size * self.size;
+            if count != expected {
+                return Err(ValidationError::ColorCount {
+                    color,
+                    count,
+                    expected,
+                });
+            }
+        }
+        if self.size != 3 {
+            return Ok(());
+        }
+
+        let actual = pieces(self);
+        let expected = pieces(&RubiksCube::new(3));
+        let corner_set = |pieces: &Pieces| counts(pieces.corners.values().map(corner_colors));
+        let corners = permutation_parity(&actual.corners, &expected.corners);
+        let edges = permutation_parity(&actual.middle_edges, &expected.middle_edges);
+        let (Some(corners), Some(edges)) = (corners, edges) else {
+            return Err(ValidationError::ImpossiblePieces);
+        };
+        if !centers_form_a_frame(self) || corner_set(&actual) != corner_set(&expected) {
+            return Err(ValidationError::ImpossiblePieces);
+        }
+
+        if actual.corners.values().map(corner_twist).sum::<usize>() % 3 != 0 {
+            return Err(ValidationError::CornerTwist);
+        }
+        let flips = actual
+            .middle_edges
+            .values()
+            .filter(|edge| edge_flipped(edge))
+            .count();
+        if flips % 2 != 0 {
+            return Err(ValidationError::EdgeFlip);
+        }
+        // The centers of a 3x3x3 only move by turning the whole cube, which permutes the
+        // corners and edges with the same parity as the centers.
+        let centers = |pieces: &Pieces| {
+            pieces
+                .fixed_centers
+                .iter()
+                .map(|(&position, &sticker)| (position, [sticker]))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let centers = permutation_parity(&centers(&actual), &centers(&expected))
+            .ok_or(ValidationError::ImpossiblePieces)?;
+        if corners ^ edges ^ centers {
+            return Err(ValidationError::PermutationParity);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the cube could be reached from solved by turning it, rather than only by
+    /// taking it apart or peeling stickers.
+    ///
+    /// Corners must be real corners with twists adding up to a whole turn, every wing and
+    /// center must be in an orbit its turns can reach, and on odd cubes the middle edges must
+    /// have an even number of flips and the corners, middle edges and fixed centers must be
+    /// permuted with matching parity. Even cubes have no such parity rule, since their centers
+    /// are interchangeable, so for example swapping two centers between faces is reachable.
+    pub fn is_reachable(&self) -> bool {
+        let n = self.size;
+        if n == 0 {
+            return true;
+        }
+        let solved = RubiksCube::new(n);
+        if n == 1 {
+            return centers_form_a_frame(self);
+        }
+
+        let actual = pieces(self);
+        let expected = pieces(&solved);
+
+        let corner_set = |pieces: &Pieces| counts(pieces.corners.values().map(corner_colors));
+        if corner_set(&actual) != corner_set(&expected)
+            || actual.corners.values().map(corner_twist).sum::<usize>() % 3 != 0
+            || actual.wings != expected.wings
+            || actual.centers != expected.centers
+        {
+            return false;
+        }
+
+        if n.is_multiple_of(2) {
+            return true;
+        }
+
+        let flips = actual
+            .middle_edges
+            .values()
+            .filter(|edge| edge_flipped(edge))
+            .count();
+        let centers = |pieces: &Pieces| {
+            pieces
+                .fixed_centers
+                .iter()
+                .map(|(&position, &sticker)| (position, [sticker]))
+                .collect::<BTreeMap<_, _>>()
+        };
+        let parities = (
+            permutation_parity(&actual.corners, &expected.corners),
+            permutation_parity(&actual.middle_edges, &expected.middle_edges),
+            permutation_parity(&centers(&actual), &centers(&expected)),
+        );
+        match parities {
+            (Some(corners), Some(edges), Some(centers)) => {
+                centers_form_a_frame(self) && flips % 2 == 0 && !(corners ^ edges ^ centers)
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn suggest_scan_fix_finds_misread_sticker() {
+        use crate::Color::*;
+        use crate::Face::*;
+        use crate::Movement::*;
+
+        let mut rc = crate::RubiksCube::new(3);
+        for (face, movement) in [(Right, Clockwise), (Up, Clockwise), (Front, Half)] {
+            crate::rotate_face(&mut rc, face, movement, 0);
+        }
+        assert_eq!(crate::suggest_scan_fix(&rc), None);
+        assert_eq!(super::misplaced_pieces(&rc), 0);
+
+        let (face, row, col) = (Left, 1, 0);
+        assert_eq!(rc.get(face, row, col), Orange);
+        rc.set(face, row, col, Red);
+        assert_eq!(
+            crate::suggest_scan_fix(&rc),
+            Some(((face, row, col), Orange))
+        );
+
+        assert_eq!(crate::suggest_scan_fix(&crate::RubiksCube::new(2)), None);
+    }
+
+    #[test]
+    fn scrambled_cubes_are_reachable() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(11);
+        for size in 1..=6 {
+            let mut rc = crate::RubiksCube::new(size);
+            assert!(rc.is_reachable(), "solved {size}x{size}x{size}");
+            for _ in 0..60 {
+                let face = num_traits::FromPrimitive::from_usize(rng.gen_range(0..6)).unwrap();
+                let movement = [
+                    crate::Movement::Clockwise,
+                    crate::Movement::CounterClockwise,
+                    crate::Movement::Half,
+                ][rng.gen_range(0..3)];
+                crate::rotate_face(&mut rc, face, movement, rng.gen_range(0..size));
+            }
+            assert!(rc.is_reachable(), "scrambled {size}x{size}x{size}");
+        }
+    }
+
+    #[test]
+    fn tampered_cubes_are_unreachable() {
+        use crate::Face::*;
+
+        let swap = |rc: &mut crate::RubiksCube,
+                    a: (crate::Face, usize, usize),
+                    b: (crate::Face, usize, usize)| {
+            let color = rc.get(a.0, a.1, a.2);
+            rc.set(a.0, a.1, a.2, rc.get(b.0, b.1, b.2));
+            rc.set(b.0, b.1, b.2, color);
+        };
+
+        // A flipped edge.
+        let mut rc = crate::RubiksCube::new(3);
+        swap(&mut rc, (Up, 2, 1), (Front, 0, 1));
+        assert!(!rc.is_reachable());
+
+        // A twisted corner.
+        let mut rc = crate::RubiksCube::new(3);
+        let corner = [(Up, 2, 2), (Front, 0, 2), (Right, 0, 0)];
+        let colors = corner.map(|(f, r, c)| rc.get(f, r, c));
+        for (i, (f, r, c)) in corner.into_iter().enumerate() {
+            rc.set(f, r, c, colors[(i + 1) % 3]);
+        }
+        assert!(!rc.is_reachable());
+
+        // Two swapped corners can't happen on a 3x3 but can on a 4x4.
+        for (size, reachable) in [(3, false), (4, true)] {
+            let mut rc = crate::RubiksCube::new(size);
+            let s = size - 1;
+            let left = [(Up, s, 0), (Left, 0, s), (Front, 0, 0)];
+            let right = [(Up, s, s), (Front, 0, s), (Right, 0, 0)];
+            let colors =
+                |corner: [(crate::Face, usize, usize); 3]| corner.map(|(f, r, c)| rc.get(f, r, c));
+            let (left_colors, right_colors) = (colors(left), colors(right));
+            for ((f, r, c), color) in left.into_iter().zip(right_colors) {
+                rc.set(f, r, c, color);
+            }
+            for ((f, r, c), color) in right.into_iter().zip(left_colors) {
+                rc.set(f, r, c, color);
+            }
+            assert_eq!(rc.is_reachable(), reachable, "{size}x{size}x{size}");
+        }
+
+        // Centers of a 4x4 can swap between faces, but can't trade places with an edge.
+        let mut rc = crate::RubiksCube::new(4);
+        swap(&mut rc, (Up, 1, 1), (Front, 1, 1));
+        assert!(rc.is_reachable());
+        swap(&mut rc, (Up, 1, 2), (Front, 0, 1));
+        assert!(!rc.is_reachable());
+
+        // A flipped wing.
+        let mut rc = crate::RubiksCube::new(4);
+        swap(&mut rc, (Up, 3, 1), (Front, 0, 1));
+        assert!(!rc.is_reachable());
+    }
+
+    #[test]
+    fn is_valid_names_the_problem() {
+        use crate::validation::ValidationError::*;
+        use crate::Color::*;
+        use crate::Face::*;
+
+        let swap = |rc: &mut crate::RubiksCube,
+                    a: (crate::Face, usize, usize),
+                    b: (crate::Face, usize, usize)| {
+            let color = rc.get(a.0, a.1, a.2);
+            rc.set(a.0, a.1, a.2, rc.get(b.0, b.1, b.2));
+            rc.set(b.0, b.1, b.2, color);
+        };
+
+        let mut rc = crate::RubiksCube::new(3);
+        crate::patterns::superflip(&mut rc).unwrap();
+        crate::rotate_face(&mut rc, Right, crate::Movement::Clockwise, 1);
+        assert_eq!(rc.is_valid(), Ok(()));
+        assert_eq!(crate::RubiksCube::new(4).is_valid(), Ok(()));
+
+        let mut rc = crate::RubiksCube::new(4);
+        rc.set(Up, 0, 0, Red);
+        assert_eq!(
+            rc.is_valid(),
+            Err(ColorCount {
+                color: Yellow,
+                count: 15,
+                expected: 16
+            })
+        );
+
+        let mut rc = crate::RubiksCube::new(3);
+        swap(&mut rc, (Up, 2, 1), (Front, 1, 0));
+        assert_eq!(rc.is_valid(), Err(ImpossiblePieces));
+
+        let mut rc = crate::RubiksCube::new(3);
+        swap(&mut rc, (Up, 2, 1), (Front, 0, 1));
+        assert_eq!(rc.is_valid(), Err(EdgeFlip));
+
+        let mut rc = crate::RubiksCube::new(3);
+        let corner = [(Up, 2, 2), (Front, 0, 2), (Right, 0, 0)];
+        let colors = corner.map(|(f, r, c)| rc.get(f, r, c));
+        for (i, (f, r, c)) in corner.into_iter().enumerate() {
+            rc.set(f, r, c, colors[(i + 1) % 3]);
+        }
+        assert_eq!(rc.is_valid(), Err(CornerTwist));
+
+        let mut rc = crate::RubiksCube::new(3);
+        swap(&mut rc, (Front, 0, 1), (Right, 0, 1));
+        assert_eq!(rc.is_valid(), Err(PermutationParity));
+    }
+}
@@ -0,0 +1,131 @@
+//! JavaScript bindings through `wasm-bindgen`, behind the `wasm` feature.
+
+use wasm_bindgen::prelude::*;
+
+use crate::{sequence_to_string, Color, RubiksCube};
+
+/// A cube as JavaScript sees it: a `RubiksCube` class with only the methods below, so the
+/// stickers cross the boundary as letters and never as the cube's own fields.
+#[wasm_bindgen(js_name = RubiksCube)]
+pub struct WasmCube {
+    cube: RubiksCube,
+}
+
+/// Builds a cube from the flat list of letters [`WasmCube::snapshot`] gives.
+fn from_letters(size: usize, letters: &[String]) -> Result<RubiksCube, String> {
+    let stickers = 6 * size * size;
+    if letters.len() != stickers {
+        return Err(format!(
+            "a {size}x{size}x{size} cube has {stickers} stickers, not {}",
+            letters.len()
+        ));
+    }
+    let colors = letters
+        .iter()
+        .map(|letter| {
+            let mut chars = letter.chars();
+            match (chars.next().and_then(Color::from_char), chars.next()) {
+                (Some(color), None) => Ok(color),
+                _ => Err(format!("{letter:?} isn't a sticker letter")),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // A cube with no stickers still has six faces, each with no rows.
+    let mut faces = colors
+        .chunks((size * size).max(1))
+        .map(|face| face.chunks(size).map(<[Color]>::to_vec).collect());
+    RubiksCube::from_faces(
+        size,
+        std::array::from_fn(|_| faces.next().unwrap_or_default()),
+    )
+}
+
+#[wasm_bindgen(js_class = RubiksCube)]
+impl WasmCube {
+    /// A solved cube of `size`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(size: usize) -> WasmCube {
+        WasmCube {
+            cube: RubiksCube::new(size),
+        }
+    }
+
+    /// A cube of `size` with the stickers listed in `letters`, as [`WasmCube::snapshot`]
+    /// writes them, so a cube can be saved on the JavaScript side and brought back.
+    #[wasm_bindgen(js_name = fromSnapshot)]
+    pub fn from_snapshot(size: usize, letters: Vec<String>) -> Result<WasmCube, JsError> {
+        let cube = from_letters(size, &letters).map_err(|e| JsError::new(&e))?;
+        Ok(WasmCube { cube })
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn size(&self) -> usize {
+        self.cube.size()
+    }
+
+    /// Makes the moves in `moves`, written in standard notation as for
+    /// [`RubiksCube::apply_wca`]. Nothing is turned if any move is invalid.
+    #[wasm_bindgen(js_name = applySequence)]
+    pub fn apply_sequence(&mut self, moves: &str) -> Result<(), JsError> {
+        let mut turned = self.cube.clone();
+        turned.apply_wca(moves).map_err(|e| JsError::new(&e))?;
+        self.cube = turned;
+        Ok(())
+    }
+
+    /// The cube's net as plain text, as [`RubiksCube::to_ascii_net`] draws it.
+    #[wasm_bindgen(js_name = toAsciiNet)]
+    pub fn to_ascii_net(&self) -> String {
+        self.cube.to_ascii_net()
+    }
+
+    /// Every sticker's letter, one per array element, in the canonical
+    /// [`RubiksCube::stickers`] order: faces U, L, F, R, B, D, each row by row.
+    pub fn snapshot(&self) -> Vec<String> {
+        self.cube
+            .stickers()
+            .map(|(.., color)| color.to_char().to_string())
+            .collect()
+    }
+
+    /// The moves that solve the cube, in standard notation separated by spaces, as found by
+    /// [`RubiksCube::solve`].
+    pub fn solve(&self) -> Result<String, JsError> {
+        let moves = self
+            .cube
+            .solve()
+            .map_err(|e| JsError::new(&e.to_string()))?;
+        Ok(sequence_to_string(&moves))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn cube_round_trips_through_letters() {
+        use super::WasmCube;
+
+        let mut cube = WasmCube::new(3);
+        cube.apply_sequence("R U R' F2 D").unwrap();
+        let letters = cube.snapshot();
+        assert_eq!(letters.len(), 54);
+        assert!(letters.iter().all(|letter| letter.len() == 1));
+
+        let restored = super::from_letters(3, &letters).unwrap();
+        assert_eq!(restored, cube.cube);
+        assert_eq!(
+            WasmCube::from_snapshot(3, letters).unwrap().to_ascii_net(),
+            cube.to_ascii_net()
+        );
+
+        let solution = cube.solve().unwrap();
+        cube.apply_sequence(&solution).unwrap();
+        assert!(cube.cube.is_solved());
+
+        assert!(super::from_letters(2, &cube.snapshot()).is_err());
+        assert_eq!(super::from_letters(0, &[]), Ok(crate::RubiksCube::new(0)));
+        let mut bad = cube.snapshot();
+        bad[5] = "Q".to_string();
+        assert!(super::from_letters(3, &bad).is_err());
+    }
+}
@@ -0,0 +1,28 @@
+// Color output is a process-wide setting, so this lives in its own test binary where it can't
+// race the unit tests that print colored cubes.
+
+#[test]
+fn disabled_colors_print_letters() {
+    rubiks_cube_solver::set_color_enabled(false);
+
+    let mut rc = rubiks_cube_solver::RubiksCube::new(2);
+    rubiks_cube_solver::rotate_face(
+        &mut rc,
+        rubiks_cube_solver::Face::Right,
+        rubiks_cube_solver::Movement::Clockwise,
+        0,
+    );
+
+    assert_eq!(
+        rc.to_string(),
+        concat!(
+            "      [Y][B]\n",
+            "      [Y][B]\n",
+            "[O][O][B][W][R][R][Y][G]\n",
+            "[O][O][B][W][R][R][Y][G]\n",
+            "      [W][G]\n",
+            "      [W][G]\n",
+        )
+    );
+    assert!(!rc.to_string().contains('\x1b'));
+}